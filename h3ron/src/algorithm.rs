@@ -91,16 +91,28 @@ mod tests {
         let polygons = ring.to_linked_polygons(false).unwrap();
         assert_eq!(polygons.len(), 1);
 
-        //let gj_in = geojson::Value::from(&polygons[0]);
-        //println!("{}", gj_in);
-
         let smoothed = smoothen_h3_linked_polygon(&polygons[0]);
 
-        //let gj_smoothed = geojson::Value::from(&smoothed);
-        //println!("{}", gj_smoothed);
-
         assert!(smoothed.exterior().coords_count() < 10);
         assert_eq!(smoothed.interiors().len(), 1);
         assert!(smoothed.interiors()[0].coords_count() < 10);
+
+        #[cfg(feature = "geojson")]
+        {
+            use crate::geojson::ToGeoJson;
+
+            // the input and the smoothed polygon can be exported for visual inspection
+            let mut data = Vec::new();
+            [polygons[0].clone(), smoothed]
+                .write_geojson(&mut data)
+                .unwrap();
+            let fc: geojson::FeatureCollection =
+                std::str::from_utf8(&data).unwrap().parse().unwrap();
+            assert_eq!(fc.features.len(), 2);
+            assert!(fc.features.iter().all(|feature| matches!(
+                feature.geometry.as_ref().map(|geometry| &geometry.value),
+                Some(geojson::Value::Polygon(rings)) if rings.len() == 2
+            )));
+        }
     }
 }
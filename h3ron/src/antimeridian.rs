@@ -0,0 +1,344 @@
+//! Handling of polygons crossing the antimeridian
+//!
+//! The polygons built by libh3 use longitudes within `[-180, 180]`. Polygons crossing the
+//! antimeridian therefore contain edges with longitude jumps of almost 360°, which most GIS
+//! software interprets as polygons spanning the whole globe. The functions of this module
+//! either normalise such polygons into a continuous longitude range or split them at the
+//! antimeridian.
+//!
+//! Polygons encircling one of the poles are closed along the pole.
+use geo::algorithm::translate::Translate;
+use geo_types::{Coordinate, LineString, MultiPolygon, Polygon};
+
+/// Selects how polygons crossing the antimeridian are handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AntimeridianHandling {
+    /// Return the polygons as built by libh3.
+    #[default]
+    None,
+
+    /// Normalise the longitudes of the polygons to a continuous range. Polygons crossing the
+    /// antimeridian will have longitudes exceeding 180°.
+    Normalize,
+
+    /// Split polygons crossing the antimeridian into multiple polygons, so all longitudes
+    /// stay within `[-180, 180]`.
+    Split,
+}
+
+impl AntimeridianHandling {
+    /// Apply the antimeridian handling to the given `polygons`.
+    pub fn apply(&self, polygons: Vec<Polygon<f64>>) -> Vec<Polygon<f64>> {
+        match self {
+            Self::None => polygons,
+            Self::Normalize => polygons.iter().map(normalize_polygon).collect(),
+            Self::Split => polygons
+                .iter()
+                .flat_map(|poly| split_polygon(poly).0)
+                .collect(),
+        }
+    }
+}
+
+/// Checks if any of the rings of `poly` contains a longitude jump of more than 180°.
+pub fn crosses_antimeridian(poly: &Polygon<f64>) -> bool {
+    ring_crosses_antimeridian(poly.exterior())
+        || poly.interiors().iter().any(ring_crosses_antimeridian)
+}
+
+fn ring_crosses_antimeridian(ring: &LineString<f64>) -> bool {
+    ring.0.windows(2).any(|w| (w[1].x - w[0].x).abs() > 180.0)
+}
+
+/// Normalise the longitudes of `poly` to a continuous range.
+///
+/// Polygons not crossing the antimeridian are returned unchanged. For polygons crossing it,
+/// the longitudes west of the antimeridian are shifted by 360°, so the resulting longitudes
+/// are within `[-180, 540]`.
+///
+/// Polygons with an empty exterior ring are returned unchanged.
+pub fn normalize_polygon(poly: &Polygon<f64>) -> Polygon<f64> {
+    if poly.exterior().0.is_empty() || !crosses_antimeridian(poly) {
+        return poly.clone();
+    }
+    let mut exterior = unwrap_ring(&poly.exterior().0, poly.exterior().0[0].x);
+    let reference_x = exterior.iter().map(|c| c.x).sum::<f64>() / exterior.len().max(1) as f64;
+    let mut interiors: Vec<_> = poly
+        .interiors()
+        .iter()
+        .filter(|ring| !ring.0.is_empty())
+        .map(|ring| unwrap_ring(&ring.0, reference_x))
+        .collect();
+
+    let min_x = exterior.iter().map(|c| c.x).fold(f64::INFINITY, f64::min);
+    if min_x < -180.0 {
+        for c in exterior.iter_mut().chain(interiors.iter_mut().flatten()) {
+            c.x += 360.0;
+        }
+    }
+    Polygon::new(
+        LineString::from(exterior),
+        interiors.into_iter().map(LineString::from).collect(),
+    )
+}
+
+/// Wrap the longitudes of `poly` back to `[-180, 180]` - the inverse of [`normalize_polygon`].
+///
+/// Rings encircling a pole keep the segments along the pole added by the normalisation.
+pub(crate) fn denormalize_polygon(poly: &Polygon<f64>) -> Polygon<f64> {
+    let wrap_ring = |ring: &LineString<f64>| {
+        ring.0
+            .iter()
+            .map(|c| Coordinate {
+                x: if c.x > 180.0 {
+                    c.x - 360.0
+                } else if c.x < -180.0 {
+                    c.x + 360.0
+                } else {
+                    c.x
+                },
+                y: c.y,
+            })
+            .collect::<Vec<_>>()
+    };
+    Polygon::new(
+        LineString::from(wrap_ring(poly.exterior())),
+        poly.interiors()
+            .iter()
+            .map(|ring| LineString::from(wrap_ring(ring)))
+            .collect(),
+    )
+}
+
+/// Split `poly` at the antimeridian.
+///
+/// Accepts polygons as built by libh3 as well as polygons using longitudes outside of
+/// `[-180, 180]` - like the ones normalised by [`normalize_polygon`].
+/// Polygons not crossing the antimeridian are returned as a `MultiPolygon` with a single
+/// polygon.
+///
+/// Concave polygons crossing the antimeridian multiple times may contain zero-width
+/// connections along the antimeridian.
+pub fn split_polygon(poly: &Polygon<f64>) -> MultiPolygon<f64> {
+    let mut normalized = normalize_polygon(poly);
+    let min_x = normalized
+        .exterior()
+        .0
+        .iter()
+        .map(|c| c.x)
+        .fold(f64::INFINITY, f64::min);
+    if min_x < -180.0 {
+        normalized.translate_inplace(360.0, 0.0);
+    }
+    if normalized
+        .exterior()
+        .0
+        .iter()
+        .all(|c| (-180.0..=180.0).contains(&c.x))
+    {
+        return MultiPolygon(vec![normalized]);
+    }
+
+    let mut polygons = Vec::with_capacity(2);
+    for (min_x, max_x, shift) in [(-180.0, 180.0, 0.0), (180.0, 540.0, -360.0)] {
+        let clip = |coords: &[Coordinate<f64>]| {
+            let mut clipped = clip_ring(&clip_ring(coords, min_x, false), max_x, true);
+            for c in clipped.iter_mut() {
+                c.x += shift;
+            }
+            clipped
+        };
+
+        let exterior = clip(&normalized.exterior().0);
+        if exterior.len() < 4 {
+            continue;
+        }
+        let interiors = normalized
+            .interiors()
+            .iter()
+            .map(|ring| clip(&ring.0))
+            .filter(|ring| ring.len() >= 4)
+            .map(LineString::from)
+            .collect();
+        polygons.push(Polygon::new(LineString::from(exterior), interiors));
+    }
+    MultiPolygon(polygons)
+}
+
+/// Remove the longitude jumps from the ring and shift it to be as close as possible
+/// to `reference_x`.
+fn unwrap_ring(coords: &[Coordinate<f64>], reference_x: f64) -> Vec<Coordinate<f64>> {
+    let mut out = Vec::with_capacity(coords.len() + 3);
+    let first = match coords.first() {
+        Some(c) => *c,
+        None => return out,
+    };
+
+    let mut offset = ((reference_x - first.x) / 360.0).round() * 360.0;
+    let mut prev_x = first.x;
+    for c in coords {
+        let dx = c.x - prev_x;
+        if dx > 180.0 {
+            offset -= 360.0;
+        } else if dx < -180.0 {
+            offset += 360.0;
+        }
+        prev_x = c.x;
+        out.push(Coordinate {
+            x: c.x + offset,
+            y: c.y,
+        });
+    }
+
+    // a ring encircling a pole does not close after unwrapping. It gets closed along the pole.
+    if let (Some(first), Some(last)) = (out.first().copied(), out.last().copied()) {
+        if (last.x - first.x).abs() > 180.0 {
+            let mean_y = out.iter().map(|c| c.y).sum::<f64>() / out.len() as f64;
+            let pole_y = if mean_y >= 0.0 { 90.0 } else { -90.0 };
+            out.push(Coordinate {
+                x: last.x,
+                y: pole_y,
+            });
+            out.push(Coordinate {
+                x: first.x,
+                y: pole_y,
+            });
+            out.push(first);
+        }
+    }
+    out
+}
+
+/// Sutherland-Hodgman clipping of a closed ring against the half-plane left (`keep_below`) or
+/// right of the longitude `x`.
+fn clip_ring(coords: &[Coordinate<f64>], x: f64, keep_below: bool) -> Vec<Coordinate<f64>> {
+    let inside = |c: &Coordinate<f64>| if keep_below { c.x <= x } else { c.x >= x };
+    let intersection = |a: &Coordinate<f64>, b: &Coordinate<f64>| Coordinate {
+        x,
+        y: (b.y - a.y).mul_add((x - a.x) / (b.x - a.x), a.y),
+    };
+
+    let mut out = Vec::with_capacity(coords.len() + 2);
+    for w in coords.windows(2) {
+        match (inside(&w[0]), inside(&w[1])) {
+            (true, true) => out.push(w[1]),
+            (true, false) => out.push(intersection(&w[0], &w[1])),
+            (false, true) => {
+                out.push(intersection(&w[0], &w[1]));
+                out.push(w[1]);
+            }
+            (false, false) => (),
+        }
+    }
+    if let Some(first) = out.first().copied() {
+        if out.last() != Some(&first) {
+            out.push(first);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use geo::algorithm::area::Area;
+    use geo::algorithm::coords_iter::CoordsIter;
+    use geo_types::{Coordinate, Polygon};
+
+    use crate::{H3Cell, ToPolygon};
+
+    use super::*;
+
+    fn antimeridian_cell() -> H3Cell {
+        H3Cell::from_coordinate(Coordinate::from((180.0, 10.0)), 3).unwrap()
+    }
+
+    fn within_lng_bounds(poly: &Polygon<f64>) -> bool {
+        poly.coords_iter().all(|c| (-180.0..=180.0).contains(&c.x))
+    }
+
+    #[test]
+    fn normalize_crossing_cell() {
+        let poly = antimeridian_cell().to_polygon().unwrap();
+        assert!(crosses_antimeridian(&poly));
+
+        let normalized = normalize_polygon(&poly);
+        assert!(!crosses_antimeridian(&normalized));
+        assert!(normalized.coords_iter().any(|c| c.x > 180.0));
+        assert!(normalized.coords_iter().all(|c| c.x > 0.0));
+
+        // not crossing polygons are unchanged
+        let cell = H3Cell::from_coordinate(Coordinate::from((10.0, 10.0)), 3).unwrap();
+        let poly = cell.to_polygon().unwrap();
+        assert!(!crosses_antimeridian(&poly));
+        assert_eq!(normalize_polygon(&poly), poly);
+    }
+
+    #[test]
+    fn split_crossing_cell() {
+        let poly = antimeridian_cell().to_polygon().unwrap();
+        let normalized = normalize_polygon(&poly);
+        let split = split_polygon(&poly);
+        assert_eq!(split.0.len(), 2);
+        for part in split.0.iter() {
+            assert!(within_lng_bounds(part));
+            assert!(!crosses_antimeridian(part));
+        }
+        assert!((split.unsigned_area() - normalized.unsigned_area()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn split_polar_cell() {
+        let cell = H3Cell::from_coordinate(Coordinate::from((0.0, 90.0)), 1).unwrap();
+        let poly = cell.to_polygon().unwrap();
+        assert!(crosses_antimeridian(&poly));
+
+        let normalized = normalize_polygon(&poly);
+        assert_eq!(
+            normalized.exterior().0.first(),
+            normalized.exterior().0.last()
+        );
+        assert!(normalized
+            .coords_iter()
+            .any(|c| (c.y - 90.0).abs() < f64::EPSILON));
+
+        let split = split_polygon(&poly);
+        assert!(!split.0.is_empty());
+        for part in split.0.iter() {
+            assert!(within_lng_bounds(part));
+        }
+        assert!((split.unsigned_area() - normalized.unsigned_area()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn denormalize_crossing_cell() {
+        let poly = antimeridian_cell().to_polygon().unwrap();
+        let denormalized = denormalize_polygon(&normalize_polygon(&poly));
+        assert!(within_lng_bounds(&denormalized));
+        for (a, b) in poly.coords_iter().zip(denormalized.coords_iter()) {
+            assert!((a.x - b.x).abs() < 1e-9);
+            assert!((a.y - b.y).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn normalize_empty_exterior() {
+        let interior = antimeridian_cell().to_polygon().unwrap().exterior().clone();
+        let poly = Polygon::new(LineString(vec![]), vec![interior]);
+        assert!(crosses_antimeridian(&poly));
+        assert_eq!(normalize_polygon(&poly), poly);
+        assert_eq!(split_polygon(&poly).0, vec![poly]);
+    }
+
+    #[test]
+    fn apply_handling() {
+        let polygons = vec![antimeridian_cell().to_polygon().unwrap()];
+        assert_eq!(AntimeridianHandling::None.apply(polygons.clone()), polygons);
+        assert_eq!(
+            AntimeridianHandling::Normalize
+                .apply(polygons.clone())
+                .len(),
+            1
+        );
+        assert_eq!(AntimeridianHandling::Split.apply(polygons).len(), 2);
+    }
+}
@@ -0,0 +1,282 @@
+//! Conversions between the collections of this crate and [Apache Arrow](https://arrow.apache.org/) arrays.
+//!
+//! Indexes are stored in `UInt64Array`s. The `0` values used by [`IndexVec`] to mark empty
+//! slots are represented as nulls. Conversions between [`IndexVec`] and `UInt64Array` avoid
+//! copying the indexes where possible.
+use std::sync::Arc;
+
+use arrow_array::types::{ArrowPrimitiveType, UInt64Type};
+use arrow_array::{Array, ArrayRef, PrimitiveArray, RecordBatch, UInt64Array};
+use arrow_buffer::{NullBuffer, ScalarBuffer};
+use arrow_schema::{ArrowError, DataType, Field, Schema};
+use h3ron_h3_sys::H3Index;
+
+use crate::collections::indexvec::IndexVec;
+use crate::collections::H3CellMap;
+#[cfg(feature = "roaring")]
+use crate::collections::H3Treemap;
+use crate::{Error, FromH3Index, H3Cell, Index};
+
+/// name of the column containing the cells in `RecordBatch`es created from `H3CellMap`s.
+pub const H3INDEX_COLUMN: &str = "h3index";
+
+/// name of the column containing the values in `RecordBatch`es created from `H3CellMap`s.
+pub const VALUE_COLUMN: &str = "value";
+
+impl From<ArrowError> for Error {
+    fn from(arrow_err: ArrowError) -> Self {
+        Self::ArrowError(arrow_err.to_string())
+    }
+}
+
+/// Converts without copying the indexes. Empty slots of the `IndexVec` become nulls.
+impl<T: FromH3Index + Index> From<IndexVec<T>> for UInt64Array {
+    fn from(index_vec: IndexVec<T>) -> Self {
+        let h3indexes: Vec<H3Index> = index_vec.into_h3index_vec();
+        let nulls = if h3indexes.contains(&0) {
+            Some(NullBuffer::from_iter(
+                h3indexes.iter().map(|h3index| *h3index != 0),
+            ))
+        } else {
+            None
+        };
+        Self::new(ScalarBuffer::from(h3indexes), nulls)
+    }
+}
+
+/// Converts without validating the indexes. Nulls become empty slots of the `IndexVec`.
+///
+/// The indexes are only copied when the memory of the array is shared or has not been
+/// allocated by rust, or when the array contains nulls.
+impl<T: FromH3Index + Index> From<UInt64Array> for IndexVec<T> {
+    fn from(array: UInt64Array) -> Self {
+        let (_, values, nulls) = array.into_parts();
+        let h3indexes = match nulls {
+            Some(nulls) if nulls.null_count() > 0 => values
+                .iter()
+                .zip(nulls.iter())
+                .map(|(h3index, is_valid)| if is_valid { *h3index } else { 0 })
+                .collect(),
+            _ => values
+                .into_inner()
+                .into_vec::<H3Index>()
+                .unwrap_or_else(|buffer| buffer.typed_data::<H3Index>().to_vec()),
+        };
+        Self::from_h3index_vec(h3indexes)
+    }
+}
+
+impl<T: FromH3Index + Index> IndexVec<T> {
+    /// Converts from an `UInt64Array` validating all indexes using [`IndexVec::iter_checked`].
+    pub fn try_from_arrow_checked(array: UInt64Array) -> Result<Self, Error> {
+        let index_vec = Self::from(array);
+        for index in index_vec.iter_checked() {
+            index?;
+        }
+        Ok(index_vec)
+    }
+}
+
+#[cfg(feature = "roaring")]
+impl<T: Index> From<&H3Treemap<T>> for UInt64Array {
+    fn from(treemap: &H3Treemap<T>) -> Self {
+        Self::from_iter_values(treemap.iter().map(|index| index.h3index()))
+    }
+}
+
+#[cfg(feature = "roaring")]
+impl<T: Index> From<&UInt64Array> for H3Treemap<T> {
+    /// Converts without validating the indexes. Nulls are skipped.
+    fn from(array: &UInt64Array) -> Self {
+        Self::from_iter_with_sort(array.iter().flatten().map(T::new))
+    }
+}
+
+#[cfg(feature = "roaring")]
+impl<T: Index> H3Treemap<T> {
+    /// Converts from an `UInt64Array` validating all indexes. Nulls are skipped.
+    pub fn try_from_arrow_checked(array: &UInt64Array) -> Result<Self, Error> {
+        Self::from_result_iter_with_sort(array.iter().flatten().map(|h3index| {
+            let index = T::new(h3index);
+            index.validate().map(|_| index)
+        }))
+    }
+}
+
+/// Converts `cell_map` to a `RecordBatch` with the columns [`H3INDEX_COLUMN`] and
+/// [`VALUE_COLUMN`]. The rows are sorted by the cells.
+pub fn cell_map_to_record_batch<P>(cell_map: &H3CellMap<P::Native>) -> Result<RecordBatch, Error>
+where
+    P: ArrowPrimitiveType,
+{
+    let mut entries: Vec<_> = cell_map.iter().collect();
+    entries.sort_unstable_by_key(|(cell, _)| **cell);
+
+    let h3indexes = UInt64Array::from_iter_values(entries.iter().map(|(cell, _)| cell.h3index()));
+    let values = PrimitiveArray::<P>::from_iter_values(entries.iter().map(|(_, value)| **value));
+
+    let schema = Schema::new(vec![
+        Field::new(H3INDEX_COLUMN, DataType::UInt64, false),
+        Field::new(VALUE_COLUMN, P::DATA_TYPE, false),
+    ]);
+    Ok(RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(h3indexes) as ArrayRef,
+            Arc::new(values) as ArrayRef,
+        ],
+    )?)
+}
+
+/// Builds a `H3CellMap` from the columns `h3index_column` and `value_column` of `record_batch`.
+///
+/// Rows with nulls in any of both columns are skipped. With `validate` all cells get
+/// validated.
+pub fn record_batch_to_cell_map<P>(
+    record_batch: &RecordBatch,
+    h3index_column: &str,
+    value_column: &str,
+    validate: bool,
+) -> Result<H3CellMap<P::Native>, Error>
+where
+    P: ArrowPrimitiveType,
+{
+    let h3indexes = column_as::<UInt64Type>(record_batch, h3index_column)?;
+    let values = column_as::<P>(record_batch, value_column)?;
+
+    let mut cell_map = H3CellMap::with_capacity_and_hasher(h3indexes.len(), Default::default());
+    for (h3index, value) in h3indexes.iter().zip(values.iter()) {
+        if let (Some(h3index), Some(value)) = (h3index, value) {
+            let cell = if validate {
+                H3Cell::try_from(h3index)?
+            } else {
+                H3Cell::new(h3index)
+            };
+            cell_map.insert(cell, value);
+        }
+    }
+    Ok(cell_map)
+}
+
+fn column_as<'a, P: ArrowPrimitiveType>(
+    record_batch: &'a RecordBatch,
+    column_name: &str,
+) -> Result<&'a PrimitiveArray<P>, Error> {
+    record_batch
+        .column_by_name(column_name)
+        .ok_or_else(|| Error::ArrowError(format!("column {} not found", column_name)))?
+        .as_any()
+        .downcast_ref::<PrimitiveArray<P>>()
+        .ok_or_else(|| {
+            Error::ArrowError(format!(
+                "column {} is not of type {}",
+                column_name,
+                P::DATA_TYPE
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_array::types::Float64Type;
+    use arrow_array::{Array, UInt64Array};
+
+    use crate::collections::indexvec::IndexVec;
+    use crate::collections::H3CellMap;
+    use crate::H3Cell;
+
+    use super::*;
+
+    fn cells() -> IndexVec<H3Cell> {
+        H3Cell::try_from(0x89283080ddbffff_u64)
+            .unwrap()
+            .grid_disk(2)
+            .unwrap()
+    }
+
+    #[test]
+    fn indexvec_roundtrip() {
+        let index_vec = cells();
+        let expected: Vec<H3Cell> = index_vec.iter().collect();
+        let ptr = index_vec.as_ptr();
+
+        let array = UInt64Array::from(index_vec);
+        assert_eq!(array.null_count(), 0);
+        assert_eq!(array.values().as_ptr(), ptr);
+
+        let index_vec_2: IndexVec<H3Cell> = IndexVec::from(array);
+        // no copying was involved
+        assert_eq!(index_vec_2.as_ptr(), ptr);
+        assert_eq!(index_vec_2.iter().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn indexvec_empty_slots_as_nulls() {
+        let mut index_vec = IndexVec::<H3Cell>::with_length(3);
+        index_vec.as_mut_slice()[1] = 0x89283080ddbffff_u64;
+        let array = UInt64Array::from(index_vec);
+        assert_eq!(array.null_count(), 2);
+        assert!(array.is_valid(1));
+
+        let index_vec_2: IndexVec<H3Cell> = IndexVec::from(array);
+        assert_eq!(index_vec_2.as_slice(), &[0, 0x89283080ddbffff_u64, 0]);
+    }
+
+    #[test]
+    fn indexvec_checked() {
+        let array = UInt64Array::from(vec![0x89283080ddbffff_u64]);
+        assert!(IndexVec::<H3Cell>::try_from_arrow_checked(array).is_ok());
+        let array = UInt64Array::from(vec![0x89283080ddbffff_u64, 55]);
+        assert!(IndexVec::<H3Cell>::try_from_arrow_checked(array).is_err());
+    }
+
+    #[cfg(feature = "roaring")]
+    #[test]
+    fn treemap_roundtrip() {
+        use crate::collections::H3Treemap;
+
+        let treemap: H3Treemap<H3Cell> = cells().iter().collect();
+        let array = UInt64Array::from(&treemap);
+        assert_eq!(array.len(), treemap.len());
+        let treemap_2: H3Treemap<H3Cell> = H3Treemap::try_from_arrow_checked(&array).unwrap();
+        assert_eq!(
+            treemap.iter().collect::<Vec<_>>(),
+            treemap_2.iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn cell_map_roundtrip() {
+        let cell_map: H3CellMap<f64> = cells()
+            .iter()
+            .map(|cell| (cell, cell.area_m2().unwrap()))
+            .collect();
+        let record_batch = cell_map_to_record_batch::<Float64Type>(&cell_map).unwrap();
+        assert_eq!(record_batch.num_rows(), cell_map.len());
+
+        let cell_map_2 = record_batch_to_cell_map::<Float64Type>(
+            &record_batch,
+            H3INDEX_COLUMN,
+            VALUE_COLUMN,
+            true,
+        )
+        .unwrap();
+        assert_eq!(cell_map, cell_map_2);
+
+        assert!(record_batch_to_cell_map::<Float64Type>(
+            &record_batch,
+            "missing",
+            VALUE_COLUMN,
+            false
+        )
+        .is_err());
+        // wrong type
+        assert!(record_batch_to_cell_map::<UInt64Type>(
+            &record_batch,
+            H3INDEX_COLUMN,
+            VALUE_COLUMN,
+            false
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,205 @@
+//! Hex-binning of points into cells.
+//!
+//! With the `use-rayon` feature the binning is done in parallel. Each thread aggregates into
+//! its own `H3CellMap` and the maps get merged afterwards.
+use geo_types::Coordinate;
+#[cfg(feature = "use-rayon")]
+use rayon::prelude::*;
+
+use crate::collections::H3CellMap;
+use crate::error::check_valid_h3_resolution;
+use crate::{Error, H3Cell};
+
+/// number of items read from the input iterator before binning them
+const BUFFER_SIZE: usize = 100_000;
+
+/// Count the coordinates - or points - located within each cell of resolution `h3_resolution`.
+///
+/// Fails on coordinates which can not be converted to cells.
+pub fn hexbin_count<I, C>(coordinates: I, h3_resolution: u8) -> Result<H3CellMap<u64>, Error>
+where
+    I: IntoIterator<Item = C>,
+    C: Into<Coordinate<f64>> + Send,
+{
+    hexbin_aggregate(
+        coordinates.into_iter().map(|c| (c, ())),
+        h3_resolution,
+        || 0_u64,
+        |count, _| *count += 1,
+        |count, other_count| *count += other_count,
+    )
+}
+
+/// Sum the weights of the coordinates - or points - located within each cell of
+/// resolution `h3_resolution`.
+///
+/// Fails on coordinates which can not be converted to cells.
+pub fn hexbin_weighted<I, C>(items: I, h3_resolution: u8) -> Result<H3CellMap<f64>, Error>
+where
+    I: IntoIterator<Item = (C, f64)>,
+    C: Into<Coordinate<f64>> + Send,
+{
+    hexbin_aggregate(
+        items,
+        h3_resolution,
+        || 0.0,
+        |sum, weight| *sum += weight,
+        |sum, other_sum| *sum += other_sum,
+    )
+}
+
+/// Aggregate the values of the coordinates - or points - located within each cell of
+/// resolution `h3_resolution` using a custom aggregation.
+///
+/// * `init` creates the initial aggregation value of a cell.
+/// * `fold` adds a value to the aggregation value of a cell.
+/// * `merge` merges two aggregation values of the same cell. This is used to combine
+///   the results of multiple threads.
+///
+/// Fails on coordinates which can not be converted to cells.
+pub fn hexbin_aggregate<I, C, V, A, FI, FF, FM>(
+    items: I,
+    h3_resolution: u8,
+    init: FI,
+    fold: FF,
+    merge: FM,
+) -> Result<H3CellMap<A>, Error>
+where
+    I: IntoIterator<Item = (C, V)>,
+    C: Into<Coordinate<f64>> + Send,
+    V: Send,
+    A: Send,
+    FI: Fn() -> A + Send + Sync,
+    FF: Fn(&mut A, V) + Send + Sync,
+    FM: Fn(&mut A, A) + Send + Sync,
+{
+    check_valid_h3_resolution(h3_resolution)?;
+    let mut cell_map = H3CellMap::default();
+    let mut iter = items.into_iter();
+    loop {
+        let buffer: Vec<_> = iter.by_ref().take(BUFFER_SIZE).collect();
+        if buffer.is_empty() {
+            break;
+        }
+        let buffer_map = bin_buffer(buffer, h3_resolution, &init, &fold, &merge)?;
+        merge_cell_maps(&mut cell_map, buffer_map, &merge);
+    }
+    Ok(cell_map)
+}
+
+fn bin_buffer<C, V, A, FI, FF, FM>(
+    buffer: Vec<(C, V)>,
+    h3_resolution: u8,
+    init: &FI,
+    fold: &FF,
+    merge: &FM,
+) -> Result<H3CellMap<A>, Error>
+where
+    C: Into<Coordinate<f64>> + Send,
+    V: Send,
+    A: Send,
+    FI: Fn() -> A + Send + Sync,
+    FF: Fn(&mut A, V) + Send + Sync,
+    FM: Fn(&mut A, A) + Send + Sync,
+{
+    let fold_item = |mut cell_map: H3CellMap<A>, (c, value): (C, V)| {
+        let cell = H3Cell::from_coordinate(c.into(), h3_resolution)?;
+        fold(cell_map.entry(cell).or_insert_with(init), value);
+        Ok(cell_map)
+    };
+
+    #[cfg(feature = "use-rayon")]
+    {
+        buffer
+            .into_par_iter()
+            .try_fold(H3CellMap::default, fold_item)
+            .try_reduce(H3CellMap::default, |mut cell_map, other_cell_map| {
+                merge_cell_maps(&mut cell_map, other_cell_map, merge);
+                Ok(cell_map)
+            })
+    }
+
+    #[cfg(not(feature = "use-rayon"))]
+    {
+        // merging is only required for the parallel implementation
+        let _ = merge;
+        buffer.into_iter().try_fold(H3CellMap::default(), fold_item)
+    }
+}
+
+fn merge_cell_maps<A, FM>(cell_map: &mut H3CellMap<A>, other_cell_map: H3CellMap<A>, merge: &FM)
+where
+    FM: Fn(&mut A, A),
+{
+    for (cell, value) in other_cell_map {
+        match cell_map.get_mut(&cell) {
+            Some(existing) => merge(existing, value),
+            None => {
+                cell_map.insert(cell, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use geo_types::{Coordinate, Point};
+
+    use crate::H3Cell;
+
+    use super::*;
+
+    fn coordinates() -> Vec<Coordinate<f64>> {
+        (0..1000_u32)
+            .map(|i| {
+                Coordinate::from((
+                    f64::from(i % 10).mul_add(0.1, 10.0),
+                    f64::from(i % 7).mul_add(0.1, 20.0),
+                ))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn count() {
+        let counts = hexbin_count(coordinates(), 6).unwrap();
+        assert_eq!(counts.values().sum::<u64>(), 1000);
+        for c in coordinates().iter() {
+            assert!(counts.contains_key(&H3Cell::from_coordinate(*c, 6).unwrap()));
+        }
+
+        let points: Vec<_> = coordinates().into_iter().map(Point::from).collect();
+        assert_eq!(hexbin_count(points, 6).unwrap(), counts);
+    }
+
+    #[test]
+    fn weighted() {
+        let sums = hexbin_weighted(coordinates().into_iter().map(|c| (c, 0.5)), 6).unwrap();
+        assert!((sums.values().sum::<f64>() - 500.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn custom_aggregation() {
+        let max_values = hexbin_aggregate(
+            coordinates()
+                .into_iter()
+                .enumerate()
+                .map(|(i, c)| (c, i as u32)),
+            6,
+            || 0_u32,
+            |max, value| *max = (*max).max(value),
+            |max, other_max| *max = (*max).max(other_max),
+        )
+        .unwrap();
+        assert_eq!(max_values.values().max(), Some(&999));
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert!(hexbin_count(coordinates(), 16).is_err());
+        assert!(hexbin_count(vec![Coordinate::from((f64::NAN, 1.0))], 6).is_err());
+        assert!(hexbin_count(Vec::<Coordinate<f64>>::new(), 6)
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,302 @@
+//! Bit-level access to the fields of H3 indexes.
+//!
+//! All functions of this module are implemented in Rust, so they avoid calls into libh3.
+//! See the [H3 index documentation](https://h3geo.org/docs/core-library/h3Indexing) for
+//! a description of the bit layout.
+
+use h3ron_h3_sys::H3Index;
+
+use crate::{Error, H3Direction, H3_MAX_RESOLUTION};
+
+/// H3 index mode of cells
+pub const H3_CELL_MODE: u8 = 1;
+
+/// H3 index mode of directed edges
+pub const H3_DIRECTED_EDGE_MODE: u8 = 2;
+
+/// H3 index mode of undirected edges
+pub const H3_EDGE_MODE: u8 = 3;
+
+/// H3 index mode of vertexes
+pub const H3_VERTEX_MODE: u8 = 4;
+
+/// Number of base cells
+pub const H3_NUM_BASE_CELLS: u8 = 122;
+
+/// The base cells which are pentagons
+const PENTAGON_BASE_CELLS: [u8; 12] = [4, 14, 24, 38, 49, 58, 63, 72, 83, 97, 107, 117];
+
+const H3_MODE_OFFSET: u64 = 59;
+const H3_MODE_MASK: u64 = 15 << H3_MODE_OFFSET;
+
+const H3_RESERVED_OFFSET: u64 = 56;
+const H3_RESERVED_MASK: u64 = 7 << H3_RESERVED_OFFSET;
+
+const H3_RES_OFFSET: u64 = 52;
+const H3_RES_MASK: u64 = 15 << H3_RES_OFFSET;
+
+const H3_BC_OFFSET: u64 = 45;
+const H3_BC_MASK: u64 = 127 << H3_BC_OFFSET;
+
+const H3_PER_DIGIT_OFFSET: u64 = 3;
+const H3_DIGIT_MASK: u64 = 7;
+
+/// all digits set to 7, all other fields set to 0
+const H3_INIT: u64 = 0x0000_1fff_ffff_ffff;
+
+/// Decodes and modifies the fields of a `H3Index`.
+///
+/// The fields are neither validated on reading nor on writing, so the
+/// resulting indexes may be invalid.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[repr(transparent)]
+pub struct H3IndexBits(H3Index);
+
+impl H3IndexBits {
+    pub const fn new(h3index: H3Index) -> Self {
+        Self(h3index)
+    }
+
+    /// the `H3Index` with all modifications applied
+    pub const fn h3index(self) -> H3Index {
+        self.0
+    }
+
+    /// the mode of the index. See the `H3_*_MODE` constants.
+    pub const fn mode(self) -> u8 {
+        ((self.0 & H3_MODE_MASK) >> H3_MODE_OFFSET) as u8
+    }
+
+    pub const fn with_mode(self, mode: u8) -> Self {
+        Self((self.0 & !H3_MODE_MASK) | (((mode as u64) << H3_MODE_OFFSET) & H3_MODE_MASK))
+    }
+
+    /// the reserved bits. These store the direction of directed edges and the vertex
+    /// number of vertexes. They are 0 for cells.
+    pub const fn reserved(self) -> u8 {
+        ((self.0 & H3_RESERVED_MASK) >> H3_RESERVED_OFFSET) as u8
+    }
+
+    pub const fn with_reserved(self, reserved: u8) -> Self {
+        Self(
+            (self.0 & !H3_RESERVED_MASK)
+                | (((reserved as u64) << H3_RESERVED_OFFSET) & H3_RESERVED_MASK),
+        )
+    }
+
+    /// the resolution of the index (0-15)
+    pub const fn resolution(self) -> u8 {
+        ((self.0 & H3_RES_MASK) >> H3_RES_OFFSET) as u8
+    }
+
+    pub const fn with_resolution(self, resolution: u8) -> Self {
+        Self((self.0 & !H3_RES_MASK) | (((resolution as u64) << H3_RES_OFFSET) & H3_RES_MASK))
+    }
+
+    /// the base cell "number" (0 to 121)
+    pub const fn base_cell(self) -> u8 {
+        ((self.0 & H3_BC_MASK) >> H3_BC_OFFSET) as u8
+    }
+
+    pub const fn with_base_cell(self, base_cell: u8) -> Self {
+        Self((self.0 & !H3_BC_MASK) | (((base_cell as u64) << H3_BC_OFFSET) & H3_BC_MASK))
+    }
+
+    /// the digit at the given `resolution` (1-15).
+    ///
+    /// Digits of resolutions finer than the resolution of the index are set to 7.
+    pub const fn digit(self, resolution: u8) -> u8 {
+        ((self.0 >> digit_offset(resolution)) & H3_DIGIT_MASK) as u8
+    }
+
+    pub const fn with_digit(self, resolution: u8, digit: u8) -> Self {
+        let offset = digit_offset(resolution);
+        Self((self.0 & !(H3_DIGIT_MASK << offset)) | (((digit as u64) & H3_DIGIT_MASK) << offset))
+    }
+
+    /// the digit at the given `resolution` (1-15) as [`H3Direction`].
+    ///
+    /// # Errors
+    ///
+    /// Fails when the digit is not a valid direction - for example for resolutions finer
+    /// than the resolution of the index.
+    pub fn direction(self, resolution: u8) -> Result<H3Direction, Error> {
+        H3Direction::try_from(self.digit(resolution))
+    }
+
+    /// checks if the base cell is one of the 12 pentagon base cells
+    pub const fn is_pentagon_base_cell(self) -> bool {
+        let base_cell = self.base_cell();
+        let mut i = 0;
+        while i < PENTAGON_BASE_CELLS.len() {
+            if PENTAGON_BASE_CELLS[i] == base_cell {
+                return true;
+            }
+            i += 1;
+        }
+        false
+    }
+
+    /// checks if the index is located on a pentagon base cell and all of its digits are
+    /// center digits. This is true for pentagon cells.
+    pub const fn is_pentagon(self) -> bool {
+        if !self.is_pentagon_base_cell() {
+            return false;
+        }
+        let mut resolution = 1;
+        while resolution <= self.resolution() {
+            if self.digit(resolution) != H3Direction::CenterDigit as u8 {
+                return false;
+            }
+            resolution += 1;
+        }
+        true
+    }
+
+    /// checks if the resolution of the index has a class III orientation
+    pub const fn is_res_class_iii(self) -> bool {
+        self.resolution() % 2 == 1
+    }
+
+    /// Builds the bits of a cell located on `base_cell` by following the given `directions`.
+    ///
+    /// The resolution of the cell is the number of given directions.
+    ///
+    /// # Errors
+    ///
+    /// Fails for invalid base cells, more than 15 directions and for paths leading into
+    /// the deleted k-subsequence of pentagons.
+    pub fn cell_from_directions(base_cell: u8, directions: &[H3Direction]) -> Result<Self, Error> {
+        if base_cell >= H3_NUM_BASE_CELLS {
+            return Err(Error::CellInvalid);
+        }
+        if directions.len() > H3_MAX_RESOLUTION as usize {
+            return Err(Error::ResDomain);
+        }
+        let mut bits = Self(H3_INIT)
+            .with_mode(H3_CELL_MODE)
+            .with_resolution(directions.len() as u8)
+            .with_base_cell(base_cell);
+
+        let mut leading_center_digits = true;
+        for (i, direction) in directions.iter().enumerate() {
+            if leading_center_digits && *direction != H3Direction::CenterDigit {
+                if *direction == H3Direction::KAxesDigit && bits.is_pentagon_base_cell() {
+                    return Err(Error::CellInvalid);
+                }
+                leading_center_digits = false;
+            }
+            bits = bits.with_digit(i as u8 + 1, *direction as u8);
+        }
+        Ok(bits)
+    }
+}
+
+impl From<H3Index> for H3IndexBits {
+    fn from(h3index: H3Index) -> Self {
+        Self::new(h3index)
+    }
+}
+
+impl From<H3IndexBits> for H3Index {
+    fn from(bits: H3IndexBits) -> Self {
+        bits.h3index()
+    }
+}
+
+#[inline(always)]
+const fn digit_offset(resolution: u8) -> u64 {
+    (H3_MAX_RESOLUTION.saturating_sub(resolution) as u64) * H3_PER_DIGIT_OFFSET
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{H3Cell, H3DirectedEdge, H3Direction, Index};
+
+    use super::*;
+
+    #[test]
+    fn decode_cell() {
+        let cell = H3Cell::new(0x8518607bfffffff);
+        let bits = H3IndexBits::new(cell.h3index());
+        assert_eq!(bits.mode(), H3_CELL_MODE);
+        assert_eq!(bits.reserved(), 0);
+        assert_eq!(bits.resolution(), 5);
+        assert_eq!(bits.base_cell(), 12);
+        assert_eq!(bits.direction(4).unwrap(), H3Direction::JkAxesDigit);
+        assert_eq!(bits.direction(5).unwrap(), H3Direction::IjAxesDigit);
+        assert_eq!(bits.digit(6), 7);
+        assert!(bits.is_res_class_iii());
+        assert!(!bits.is_pentagon());
+    }
+
+    #[test]
+    fn decode_directed_edge() {
+        let edge = H3DirectedEdge::new(0x149283080ddbffff);
+        let bits = H3IndexBits::new(edge.h3index());
+        assert_eq!(bits.mode(), H3_DIRECTED_EDGE_MODE);
+        assert_eq!(bits.resolution(), 9);
+        assert_eq!(
+            bits.with_mode(H3_CELL_MODE).with_reserved(0).h3index(),
+            edge.origin_cell().unwrap().h3index()
+        );
+    }
+
+    #[test]
+    fn pentagons() {
+        let pentagon = H3IndexBits::new(0x821c07fffffffff);
+        assert!(pentagon.is_pentagon_base_cell());
+        assert!(pentagon.is_pentagon());
+
+        let child = H3Cell::new(0x821c07fffffffff)
+            .get_children(3)
+            .unwrap()
+            .iter()
+            .last()
+            .unwrap();
+        assert!(H3IndexBits::new(child.h3index()).is_pentagon_base_cell());
+        assert!(!H3IndexBits::new(child.h3index()).is_pentagon());
+    }
+
+    #[test]
+    fn modify_fields() {
+        let bits = H3IndexBits::new(0x8518607bfffffff);
+        assert_eq!(bits.with_resolution(3).resolution(), 3);
+        assert_eq!(bits.with_base_cell(121).base_cell(), 121);
+        assert_eq!(bits.with_base_cell(121).resolution(), 5);
+        assert_eq!(bits.with_digit(2, 3).digit(2), 3);
+        assert_eq!(bits.with_digit(2, 3).digit(3), bits.digit(3));
+        assert_eq!(bits.with_mode(H3_EDGE_MODE).mode(), H3_EDGE_MODE);
+        assert_eq!(bits.with_reserved(5).reserved(), 5);
+        assert_eq!(bits.with_reserved(5).with_reserved(0), bits);
+    }
+
+    #[test]
+    fn cell_from_directions_roundtrip() {
+        let cell = H3Cell::new(0x861ea54f7ffffff);
+        let bits = H3IndexBits::new(cell.h3index());
+        let directions = H3Direction::iter_directions_over_resolutions(&cell)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let built = H3IndexBits::cell_from_directions(bits.base_cell(), &directions).unwrap();
+        assert_eq!(built.h3index(), cell.h3index());
+    }
+
+    #[test]
+    fn cell_from_directions_invalid() {
+        assert!(H3IndexBits::cell_from_directions(122, &[]).is_err());
+        assert!(H3IndexBits::cell_from_directions(0, &[H3Direction::CenterDigit; 16]).is_err());
+
+        // deleted k-subsequence of pentagons
+        assert!(H3IndexBits::cell_from_directions(
+            4,
+            &[H3Direction::CenterDigit, H3Direction::KAxesDigit]
+        )
+        .is_err());
+        assert!(H3IndexBits::cell_from_directions(
+            4,
+            &[H3Direction::IAxesDigit, H3Direction::KAxesDigit]
+        )
+        .is_ok());
+    }
+}
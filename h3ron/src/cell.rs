@@ -1,29 +1,34 @@
-use std::fmt::{self, Debug, Formatter};
-use std::ops::Deref;
+use std::fmt::{self, Debug, Display, Formatter, LowerHex};
+use std::ops::{Deref, RangeInclusive};
 use std::os::raw::c_int;
 use std::str::FromStr;
 
 use geo_types::{Coordinate, Point, Polygon};
 #[cfg(feature = "use-serde")]
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use h3ron_h3_sys::H3Index;
 
 use crate::collections::indexvec::IndexVec;
-use crate::error::Error;
+use crate::error::{check_valid_h3_resolution, Error};
+#[cfg(feature = "use-serde")]
+use crate::index::{deserialize_index, serialize_index};
 use crate::index::{index_from_str, Index};
 use crate::iter::CellBoundaryBuilder;
-use crate::{max_grid_disk_size, FromH3Index, H3DirectedEdge, ToCoordinate, ToPolygon};
+use crate::{
+    great_circle_distance_km, great_circle_distance_m, great_circle_distance_rads,
+    max_grid_disk_size, FromH3Index, H3DirectedEdge, H3Direction, H3IndexBits, H3Vertex,
+    ToCoordinate, ToPolygon,
+};
 
 /// H3 Index representing a H3 Cell (hexagon)
 #[derive(PartialOrd, PartialEq, Clone, Hash, Eq, Ord, Copy)]
-#[cfg_attr(feature = "use-serde", derive(Serialize, Deserialize))]
 #[repr(transparent)]
 pub struct H3Cell(H3Index);
 
 impl Debug for H3Cell {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "H3Cell({})", self.to_string())
+        write!(f, "H3Cell({})", self)
     }
 }
 
@@ -129,6 +134,124 @@ impl H3Cell {
         Ok(index_vec)
     }
 
+    /// Number of children of `self` at resolution `child_resolution`.
+    ///
+    /// In contrast to [`H3Cell::get_children`] this does not allocate.
+    pub fn children_count(&self, child_resolution: u8) -> Result<u64, Error> {
+        check_valid_h3_resolution(child_resolution)?;
+        let resolution = self.resolution();
+        if child_resolution < resolution {
+            return Err(Error::ResMismatch);
+        }
+        let num_levels = u32::from(child_resolution - resolution);
+        Ok(if self.is_pentagon() {
+            pentagon_children_count(num_levels)
+        } else {
+            7_u64.pow(num_levels)
+        })
+    }
+
+    /// The numeric range of the h3indexes of all children of `self` at resolution
+    /// `child_resolution`.
+    ///
+    /// All children are contained in this range, and all valid cells of resolution
+    /// `child_resolution` within this range are children of `self`. This allows finding
+    /// the children in sorted collections using range scans.
+    pub fn children_h3index_range(
+        &self,
+        child_resolution: u8,
+    ) -> Result<RangeInclusive<H3Index>, Error> {
+        check_valid_h3_resolution(child_resolution)?;
+        let resolution = self.resolution();
+        if child_resolution < resolution {
+            return Err(Error::ResMismatch);
+        }
+        let mut first = self.bits().with_resolution(child_resolution);
+        let mut last = first;
+        for digit_resolution in (resolution + 1)..=child_resolution {
+            first = first.with_digit(digit_resolution, 0);
+            last = last.with_digit(digit_resolution, 6);
+        }
+        Ok(first.h3index()..=last.h3index())
+    }
+
+    /// The position of `self` within the ordered children of its parent at
+    /// `parent_resolution`.
+    ///
+    /// The children are ordered in the same way as returned by [`H3Cell::get_children`], so
+    /// the position is an offset into that list. This is the reverse of [`H3Cell::child_at_position`].
+    pub fn child_position(&self, parent_resolution: u8) -> Result<u64, Error> {
+        let bits = self.bits();
+        let resolution = bits.resolution();
+        if parent_resolution > resolution {
+            return Err(Error::ResMismatch);
+        }
+
+        let mut in_pentagon = bits.with_resolution(parent_resolution).is_pentagon();
+        let mut position = 0_u64;
+        for digit_resolution in (parent_resolution + 1)..=resolution {
+            let hexagon_width = 7_u64.pow(u32::from(resolution - digit_resolution));
+            let digit = H3Direction::try_from(bits.digit(digit_resolution))
+                .map_err(|_| Error::CellInvalid)?;
+            if in_pentagon {
+                match digit {
+                    H3Direction::CenterDigit => continue,
+                    // deleted k-subsequence of pentagons
+                    H3Direction::KAxesDigit => return Err(Error::CellInvalid),
+                    _ => {
+                        // the children of the center child of the pentagon come first, followed
+                        // by the children of the hexagons, starting with the j-axes digit.
+                        position +=
+                            pentagon_children_count(u32::from(resolution - digit_resolution))
+                                + (digit as u64 - 2) * hexagon_width;
+                        in_pentagon = false;
+                    }
+                }
+            } else {
+                position += digit as u64 * hexagon_width;
+            }
+        }
+        Ok(position)
+    }
+
+    /// The child of `self` at resolution `child_resolution` located at `position` within
+    /// the ordered children.
+    ///
+    /// The children are ordered in the same way as returned by [`H3Cell::get_children`], so this
+    /// allows accessing the n-th child without building the complete list. This is the reverse
+    /// of [`H3Cell::child_position`].
+    pub fn child_at_position(&self, position: u64, child_resolution: u8) -> Result<Self, Error> {
+        if position >= self.children_count(child_resolution)? {
+            return Err(Error::Domain);
+        }
+        let resolution = self.resolution();
+        let mut bits = self.bits().with_resolution(child_resolution);
+        let mut in_pentagon = self.is_pentagon();
+        let mut remaining = position;
+        for digit_resolution in (resolution + 1)..=child_resolution {
+            let num_levels = u32::from(child_resolution - digit_resolution);
+            let hexagon_width = 7_u64.pow(num_levels);
+            let digit = if in_pentagon {
+                let pentagon_width = pentagon_children_count(num_levels);
+                if remaining < pentagon_width {
+                    H3Direction::CenterDigit as u64
+                } else {
+                    remaining -= pentagon_width;
+                    in_pentagon = false;
+                    let digit = remaining / hexagon_width + 2;
+                    remaining %= hexagon_width;
+                    digit
+                }
+            } else {
+                let digit = remaining / hexagon_width;
+                remaining %= hexagon_width;
+                digit
+            };
+            bits = bits.with_digit(digit_resolution, digit as u8);
+        }
+        Ok(Self(bits.h3index()))
+    }
+
     /// Checks if the current index and `other` are neighbors.
     pub fn are_neighbor_cells(&self, other: Self) -> Result<bool, Error> {
         let mut res: i32 = 0;
@@ -167,6 +290,24 @@ impl H3Cell {
         .map(|_| index_vec)
     }
 
+    /// hollow hexagon ring at `self`
+    ///
+    /// In contrast to [`H3Cell::grid_ring_unsafe`] this function does not fail when
+    /// encountering pentagonal distortion. In that case the ring is built using
+    /// [`H3Cell::grid_disk_distances`], which is slower.
+    pub fn grid_ring(&self, k: u32) -> Result<IndexVec<Self>, Error> {
+        match self.grid_ring_unsafe(k) {
+            Err(Error::Pentagon) => {
+                let mut index_vec = IndexVec::new();
+                for (_, cell) in self.grid_disk_distances(k, k)? {
+                    index_vec.push(cell);
+                }
+                Ok(index_vec)
+            }
+            result => result,
+        }
+    }
+
     /// Retrieves indexes around `self` through K Rings.
     ///
     /// # Arguments
@@ -243,12 +384,50 @@ impl H3Cell {
 
     /// determines if an H3 cell is a pentagon
     pub fn is_pentagon(&self) -> bool {
-        unsafe { h3ron_h3_sys::isPentagon(self.0) == 1 }
+        self.bits().is_pentagon()
     }
 
     /// returns the base cell "number" (0 to 121) of the provided H3 cell
     pub fn get_base_cell_number(&self) -> u8 {
-        unsafe { h3ron_h3_sys::getBaseCellNumber(self.0) as u8 }
+        self.bits().base_cell()
+    }
+
+    /// The icosahedron faces intersected by `self`, ordered by their number.
+    ///
+    /// Cells intersecting more than one face may cause failures of functions like
+    /// [`H3Cell::grid_ring_unsafe`] or the functions of the [`crate::localij`] module.
+    pub fn icosahedron_faces(&self) -> Result<Vec<u8>, Error> {
+        let mut max_face_count: c_int = 0;
+        Error::check_returncode(unsafe {
+            h3ron_h3_sys::maxFaceCount(self.0, &mut max_face_count)
+        })?;
+        let mut faces: Vec<c_int> = vec![-1; max_face_count as usize];
+        Error::check_returncode(unsafe {
+            h3ron_h3_sys::getIcosahedronFaces(self.0, faces.as_mut_ptr())
+        })?;
+
+        // unused slots are filled with -1
+        let mut faces: Vec<_> = faces
+            .into_iter()
+            .filter(|face| *face >= 0)
+            .map(|face| face as u8)
+            .collect();
+        faces.sort_unstable();
+        Ok(faces)
+    }
+
+    /// Builds the cell located on `base_cell` by following the given `directions`. The
+    /// resolution of the cell is the number of `directions`.
+    ///
+    /// # Errors
+    ///
+    /// Fails for invalid base cells, more than 15 directions and for paths leading into
+    /// the deleted k-subsequence of pentagons.
+    pub fn from_base_cell_and_directions(
+        base_cell: u8,
+        directions: &[H3Direction],
+    ) -> Result<Self, Error> {
+        H3IndexBits::cell_from_directions(base_cell, directions).map(|bits| Self(bits.h3index()))
     }
 
     /// Gets the directed edge from `self` to `destination`
@@ -280,6 +459,29 @@ impl H3Cell {
         .map(|_| index_vec)
     }
 
+    /// Retrieves the vertex `vertex_num` of `self`.
+    ///
+    /// Hexagons have the vertexes `0..=5`, pentagons `0..=4`. The vertexes are shared
+    /// with the neighboring cells, so all cells meeting at a corner return the same [`H3Vertex`].
+    pub fn vertex(&self, vertex_num: u8) -> Result<H3Vertex, Error> {
+        let mut vertex_h3index: H3Index = 0;
+        Error::check_returncode(unsafe {
+            h3ron_h3_sys::cellToVertex(self.h3index(), c_int::from(vertex_num), &mut vertex_h3index)
+        })
+        .map(|_| H3Vertex::new(vertex_h3index))
+    }
+
+    /// Retrieves all vertexes of `self`.
+    ///
+    /// The vertexes are ordered like the vertexes of the cell boundary.
+    pub fn vertexes(&self) -> Result<IndexVec<H3Vertex>, Error> {
+        let mut index_vec = IndexVec::with_length(6);
+        Error::check_returncode(unsafe {
+            h3ron_h3_sys::cellToVertexes(self.h3index(), index_vec.as_mut_ptr())
+        })
+        .map(|_| index_vec)
+    }
+
     /// get the average cell area at `resolution` in square meters.
     ///
     /// ```
@@ -325,6 +527,30 @@ impl H3Cell {
             .map(|_| area)
     }
 
+    /// The great circle distance between the centroids of `self` and `other` in radians.
+    pub fn distance_rads_to(&self, other: &Self) -> Result<f64, Error> {
+        Ok(great_circle_distance_rads(
+            &self.to_coordinate()?,
+            &other.to_coordinate()?,
+        ))
+    }
+
+    /// The great circle distance between the centroids of `self` and `other` in kilometers.
+    pub fn distance_km_to(&self, other: &Self) -> Result<f64, Error> {
+        Ok(great_circle_distance_km(
+            &self.to_coordinate()?,
+            &other.to_coordinate()?,
+        ))
+    }
+
+    /// The great circle distance between the centroids of `self` and `other` in meters.
+    pub fn distance_m_to(&self, other: &Self) -> Result<f64, Error> {
+        Ok(great_circle_distance_m(
+            &self.to_coordinate()?,
+            &other.to_coordinate()?,
+        ))
+    }
+
     /// returns the center child of `self` at the specified resolution.
     pub fn center_child(&self, resolution: u8) -> Result<Self, Error> {
         let mut cell_index: H3Index = 0;
@@ -339,9 +565,15 @@ impl H3Cell {
     }
 }
 
-impl ToString for H3Cell {
-    fn to_string(&self) -> String {
-        format!("{:x}", self.0)
+impl Display for H3Cell {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:x}", self.0)
+    }
+}
+
+impl LowerHex for H3Cell {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        LowerHex::fmt(&self.0, f)
     }
 }
 
@@ -353,6 +585,27 @@ impl FromStr for H3Cell {
     }
 }
 
+/// Serialized as hex string for human-readable formats like JSON, as `u64` otherwise.
+#[cfg(feature = "use-serde")]
+impl Serialize for H3Cell {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_index(self, "H3Cell", serializer)
+    }
+}
+
+#[cfg(feature = "use-serde")]
+impl<'de> Deserialize<'de> for H3Cell {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_index("H3Cell", deserializer)
+    }
+}
+
 impl ToPolygon for H3Cell {
     type Error = Error;
 
@@ -383,6 +636,11 @@ impl Deref for H3Cell {
     }
 }
 
+/// Number of descendants of a pentagon `num_levels` resolutions finer than the pentagon.
+const fn pentagon_children_count(num_levels: u32) -> u64 {
+    1 + 5 * (7_u64.pow(num_levels) - 1) / 6
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
@@ -394,7 +652,7 @@ mod tests {
     use h3ron_h3_sys::H3Index;
 
     use crate::cell::H3Cell;
-    use crate::Index;
+    use crate::{H3Direction, Index};
 
     #[test]
     fn test_h3_to_string() {
@@ -417,6 +675,22 @@ mod tests {
         assert_eq!(H3Cell::try_from(0x89283080ddbffff_u64).unwrap(), index);
     }
 
+    #[test]
+    fn test_string_with_prefix_to_h3() {
+        let cell = H3Cell::try_from(0x89283080ddbffff_u64).unwrap();
+        assert_eq!(H3Cell::from_str("0x89283080ddbffff").unwrap(), cell);
+        assert_eq!(H3Cell::from_str("0X89283080ddbffff").unwrap(), cell);
+        assert!(H3Cell::from_str("0x").is_err());
+    }
+
+    #[test]
+    fn test_lower_hex() {
+        let cell = H3Cell::new(0x89283080ddbffff_u64);
+        assert_eq!(format!("{:x}", cell), "89283080ddbffff");
+        assert_eq!(format!("{:#x}", cell), "0x89283080ddbffff");
+        assert_eq!(H3Cell::from_str(&format!("{:#x}", cell)).unwrap(), cell);
+    }
+
     #[test]
     fn test_is_valid() {
         assert!(H3Cell::try_from(0x89283080ddbffff_u64).unwrap().is_valid());
@@ -424,6 +698,140 @@ mod tests {
         assert!(H3Cell::try_from(0_u64).is_err());
     }
 
+    #[test]
+    fn test_from_base_cell_and_directions() {
+        let cell = H3Cell::try_from(0x89283080ddbffff_u64).unwrap();
+        let directions = H3Direction::iter_directions_over_resolutions(&cell)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let built = H3Cell::from_base_cell_and_directions(cell.get_base_cell_number(), &directions)
+            .unwrap();
+        assert_eq!(built, cell);
+        assert!(built.is_valid());
+    }
+
+    #[test]
+    fn test_child_position_hexagon() {
+        let cell = H3Cell::try_from(0x8518607bfffffff_u64).unwrap();
+        let children = cell.get_children(7).unwrap();
+        assert_eq!(cell.children_count(7).unwrap(), children.count() as u64);
+        for (i, child) in children.iter().enumerate() {
+            assert_eq!(child.child_position(5).unwrap(), i as u64);
+            assert_eq!(cell.child_at_position(i as u64, 7).unwrap(), child);
+        }
+        assert!(cell.child_at_position(49, 7).is_err());
+    }
+
+    #[test]
+    fn test_child_position_pentagon() {
+        let cell = H3Cell::try_from(0x821c07fffffffff_u64).unwrap();
+        let children = cell.get_children(5).unwrap();
+        assert_eq!(cell.children_count(5).unwrap(), children.count() as u64);
+        for (i, child) in children.iter().enumerate() {
+            assert_eq!(child.child_position(2).unwrap(), i as u64);
+            assert_eq!(cell.child_at_position(i as u64, 5).unwrap(), child);
+        }
+        assert!(cell.child_at_position(children.count() as u64, 5).is_err());
+    }
+
+    #[test]
+    fn test_children_h3index_range() {
+        let cell = H3Cell::try_from(0x8518607bfffffff_u64).unwrap();
+        let range = cell.children_h3index_range(7).unwrap();
+        let children = cell.get_children(7).unwrap();
+        assert_eq!(children.iter().min().unwrap().h3index(), *range.start());
+        assert_eq!(children.iter().max().unwrap().h3index(), *range.end());
+        assert!(children
+            .iter()
+            .all(|child| range.contains(&child.h3index())));
+
+        // the parent of the cell is outside of the range, as are the neighbors of the cell
+        assert!(!range.contains(&cell.get_parent(4).unwrap().h3index()));
+        for neighbor in cell.grid_ring(1).unwrap().iter() {
+            let neighbor_range = neighbor.children_h3index_range(7).unwrap();
+            assert!(neighbor_range.end() < range.start() || neighbor_range.start() > range.end());
+        }
+
+        assert_eq!(
+            cell.children_h3index_range(5).unwrap(),
+            cell.h3index()..=cell.h3index()
+        );
+        assert!(cell.children_h3index_range(4).is_err());
+    }
+
+    #[test]
+    fn test_child_position_invalid_resolution() {
+        let cell = H3Cell::try_from(0x8518607bfffffff_u64).unwrap();
+        assert!(cell.child_position(6).is_err());
+        assert_eq!(cell.child_position(5).unwrap(), 0);
+        assert!(cell.child_at_position(0, 4).is_err());
+        assert!(cell.child_at_position(0, 16).is_err());
+    }
+
+    #[test]
+    fn test_great_circle_distance_to() {
+        use geo::algorithm::haversine_distance::HaversineDistance;
+        use geo_types::Point;
+
+        use crate::ToCoordinate;
+
+        let cell = H3Cell::try_from(0x89283080ddbffff_u64).unwrap();
+        assert!(cell.distance_m_to(&cell).unwrap().abs() < f64::EPSILON);
+
+        let disk = cell.grid_disk(1).unwrap();
+        let distances = disk.distances_m_to(&cell).unwrap();
+        assert_eq!(distances.len(), 7);
+        for (neighbor, distance) in disk.iter().zip(distances.iter()) {
+            assert!((neighbor.distance_m_to(&cell).unwrap() - distance).abs() < 1e-6);
+            assert!((neighbor.distance_km_to(&cell).unwrap() * 1000.0 - distance).abs() < 1e-6);
+            // compare with the haversine implementation of geo, which uses a slightly
+            // different earth radius
+            let geo_distance = Point::from(neighbor.to_coordinate().unwrap())
+                .haversine_distance(&Point::from(cell.to_coordinate().unwrap()));
+            assert!((distance - geo_distance).abs() <= geo_distance * 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_icosahedron_faces() {
+        let cell = H3Cell::try_from(0x89283080ddbffff_u64).unwrap();
+        assert_eq!(cell.icosahedron_faces().unwrap(), vec![7]);
+
+        let pentagon = H3Cell::try_from(0x821c07fffffffff_u64).unwrap();
+        assert_eq!(pentagon.icosahedron_faces().unwrap().len(), 5);
+    }
+
+    #[test]
+    fn test_grid_ring() {
+        let cell = H3Cell::try_from(0x89283080ddbffff_u64).unwrap();
+        let mut ring: Vec<_> = cell.grid_ring(2).unwrap().into();
+        let mut ring_unsafe: Vec<_> = cell.grid_ring_unsafe(2).unwrap().into();
+        ring.sort_unstable();
+        ring_unsafe.sort_unstable();
+        assert_eq!(ring, ring_unsafe);
+        assert_eq!(ring.len(), 12);
+    }
+
+    #[test]
+    fn test_grid_ring_pentagon() {
+        let pentagon = H3Cell::try_from(0x821c07fffffffff_u64).unwrap();
+        assert!(pentagon.grid_ring_unsafe(1).is_err());
+        assert_eq!(pentagon.grid_ring(1).unwrap().count(), 5);
+
+        // a cell with the pentagon in its ring
+        let neighbor = pentagon.grid_ring(1).unwrap().first().unwrap();
+        let mut ring: Vec<_> = neighbor.grid_ring(2).unwrap().into();
+        ring.sort_unstable();
+        let mut expected: Vec<_> = neighbor
+            .grid_disk_distances(2, 2)
+            .unwrap()
+            .into_iter()
+            .map(|(_, cell)| cell)
+            .collect();
+        expected.sort_unstable();
+        assert_eq!(ring, expected);
+    }
+
     #[test]
     fn test_eq() {
         assert_eq!(
@@ -509,6 +917,70 @@ mod tests {
         assert_eq!(idx.h3index(), idx_2.h3index());
     }
 
+    #[cfg(feature = "use-serde")]
+    #[test]
+    fn serde_index_human_readable() {
+        use serde::de::value::{
+            Error as DeError, I64Deserializer, StrDeserializer, U64Deserializer,
+        };
+        use serde::de::IntoDeserializer;
+        use serde::Deserialize;
+
+        let cell = H3Cell::new(0x89283080ddbffff_u64);
+        let deserializer: StrDeserializer<DeError> = "89283080ddbffff".into_deserializer();
+        assert_eq!(H3Cell::deserialize(deserializer).unwrap(), cell);
+
+        let deserializer: StrDeserializer<DeError> = "0x89283080ddbffff".into_deserializer();
+        assert_eq!(H3Cell::deserialize(deserializer).unwrap(), cell);
+
+        let deserializer: StrDeserializer<DeError> = "invalid".into_deserializer();
+        assert!(H3Cell::deserialize(deserializer).is_err());
+
+        // decimal strings as written by earlier versions - for example as keys of JSON maps
+        let decimal = cell.h3index().to_string();
+        let deserializer: StrDeserializer<DeError> = decimal.as_str().into_deserializer();
+        assert_eq!(H3Cell::deserialize(deserializer).unwrap(), cell);
+
+        let deserializer: StrDeserializer<DeError> = "99999999999999999999".into_deserializer();
+        assert!(H3Cell::deserialize(deserializer).is_err());
+
+        // plain numbers are still accepted
+        let deserializer: U64Deserializer<DeError> = cell.h3index().into_deserializer();
+        assert_eq!(H3Cell::deserialize(deserializer).unwrap(), cell);
+
+        let deserializer: I64Deserializer<DeError> = (cell.h3index() as i64).into_deserializer();
+        assert_eq!(H3Cell::deserialize(deserializer).unwrap(), cell);
+
+        // numbers get validated just like strings
+        let deserializer: U64Deserializer<DeError> = 0_u64.into_deserializer();
+        assert!(H3Cell::deserialize(deserializer).is_err());
+
+        let deserializer: I64Deserializer<DeError> = (-1_i64).into_deserializer();
+        assert!(H3Cell::deserialize(deserializer).is_err());
+    }
+
+    #[cfg(all(feature = "use-serde", feature = "geojson"))]
+    #[test]
+    fn serde_cell_map_json_keys() {
+        use crate::collections::H3CellMap;
+
+        let cell = H3Cell::new(0x85283473fffffff_u64);
+        let cell_map: H3CellMap<u32> = std::iter::once((cell, 1)).collect();
+        let json = serde_json::to_string(&cell_map).unwrap();
+        assert_eq!(json, r#"{"85283473fffffff":1}"#);
+        assert_eq!(
+            serde_json::from_str::<H3CellMap<u32>>(&json).unwrap(),
+            cell_map
+        );
+
+        // decimal keys as written by earlier versions
+        let json = format!(r#"{{"{}":1}}"#, cell.h3index());
+        assert_eq!(
+            serde_json::from_str::<H3CellMap<u32>>(&json).unwrap(),
+            cell_map
+        );
+    }
+
     /// this test is not really a hard requirement, but it is nice to know
     /// Index is handled just like an u64
     #[cfg(feature = "use-serde")]
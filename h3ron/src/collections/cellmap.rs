@@ -0,0 +1,278 @@
+//! Changing the resolution of [`H3CellMap`]s including their values.
+//!
+//! * [`roll_up`] aggregates the values to a coarser resolution. The functions [`sum`], [`mean`],
+//!   [`max`] and [`min`] can be used as aggregation functions.
+//! * [`drill_down`] pushes the values down to the children at a finer resolution. The
+//!   functions [`split_copy`] and [`split_divide`] can be used as split strategies.
+use std::iter::Sum;
+
+use crate::collections::H3CellMap;
+use crate::iter::change_resolution_tuple;
+use crate::{Error, Index};
+
+/// Aggregate the values of `cell_map` to the parent cells at `output_h3_resolution` using
+/// the `aggregate` function.
+///
+/// `aggregate` receives the values of all cells located within the same parent cell.
+///
+/// Fails for cells with a coarser resolution than `output_h3_resolution`.
+pub fn roll_up<V, A, F>(
+    cell_map: &H3CellMap<V>,
+    output_h3_resolution: u8,
+    aggregate: F,
+) -> Result<H3CellMap<A>, Error>
+where
+    F: Fn(&[&V]) -> A,
+{
+    if cell_map
+        .keys()
+        .any(|cell| cell.resolution() < output_h3_resolution)
+    {
+        return Err(Error::ResMismatch);
+    }
+
+    let mut grouped: H3CellMap<Vec<&V>> = H3CellMap::default();
+    for cell_tuple in change_resolution_tuple(cell_map.keys(), output_h3_resolution) {
+        let (cell, parent) = cell_tuple?;
+        if let Some(value) = cell_map.get(&cell) {
+            grouped.entry(parent).or_default().push(value);
+        }
+    }
+    Ok(grouped
+        .into_iter()
+        .map(|(parent, values)| (parent, aggregate(&values)))
+        .collect())
+}
+
+/// Push the values of `cell_map` down to the child cells at `output_h3_resolution` using
+/// the `split` function.
+///
+/// `split` receives the value of the parent and the number of its children at
+/// `output_h3_resolution`.
+///
+/// Fails for cells with a finer resolution than `output_h3_resolution` and with
+/// [`Error::DuplicateInput`] when cells of `cell_map` overlap - like a cell and one of its
+/// descendants.
+pub fn drill_down<V, O, F>(
+    cell_map: &H3CellMap<V>,
+    output_h3_resolution: u8,
+    split: F,
+) -> Result<H3CellMap<O>, Error>
+where
+    O: Clone,
+    F: Fn(&V, u64) -> O,
+{
+    if cell_map
+        .keys()
+        .any(|cell| cell.resolution() > output_h3_resolution)
+    {
+        return Err(Error::ResMismatch);
+    }
+
+    let mut out = H3CellMap::default();
+    for (cell, value) in cell_map.iter() {
+        let child_value = split(value, cell.children_count(output_h3_resolution)?);
+        for cell_tuple in change_resolution_tuple(std::iter::once(cell), output_h3_resolution) {
+            let (_, child) = cell_tuple?;
+            if out.insert(child, child_value.clone()).is_some() {
+                return Err(Error::DuplicateInput);
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Sum of the values
+pub fn sum<V>(values: &[&V]) -> V
+where
+    V: Sum<V> + Copy,
+{
+    values.iter().map(|v| **v).sum()
+}
+
+/// Arithmetic mean of the values
+pub fn mean<V>(values: &[&V]) -> f64
+where
+    V: ToF64,
+{
+    values.iter().map(|v| v.to_f64()).sum::<f64>() / values.len() as f64
+}
+
+/// Maximum of the values
+///
+/// # Panics
+///
+/// Panics when `values` is empty - which will never be the case when called by [`roll_up`].
+pub fn max<V>(values: &[&V]) -> V
+where
+    V: PartialOrd + Copy,
+{
+    values
+        .iter()
+        .skip(1)
+        .fold(*values[0], |acc, v| if **v > acc { **v } else { acc })
+}
+
+/// Minimum of the values
+///
+/// # Panics
+///
+/// Panics when `values` is empty - which will never be the case when called by [`roll_up`].
+pub fn min<V>(values: &[&V]) -> V
+where
+    V: PartialOrd + Copy,
+{
+    values
+        .iter()
+        .skip(1)
+        .fold(*values[0], |acc, v| if **v < acc { **v } else { acc })
+}
+
+/// Split strategy for [`drill_down`] assigning the value of the parent to all children.
+pub fn split_copy<V: Clone>(value: &V, _child_count: u64) -> V {
+    value.clone()
+}
+
+/// Split strategy for [`drill_down`] dividing the value of the parent by the number of
+/// children.
+pub fn split_divide<V>(value: &V, child_count: u64) -> f64
+where
+    V: ToF64,
+{
+    value.to_f64() / child_count as f64
+}
+
+/// Conversion of numeric values to `f64` as used by [`mean`] and [`split_divide`].
+///
+/// In contrast to `Into<f64>` this is also implemented for `u64`, `i64`, `usize` and `isize` -
+/// accepting the possible loss of precision for large values.
+pub trait ToF64 {
+    fn to_f64(&self) -> f64;
+}
+
+macro_rules! impl_to_f64 {
+    ($($t:ty),*) => {
+        $(
+            impl ToF64 for $t {
+                fn to_f64(&self) -> f64 {
+                    *self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_to_f64!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+
+#[cfg(test)]
+mod tests {
+    use crate::binning::hexbin_count;
+    use crate::collections::H3CellMap;
+    use crate::{H3Cell, Index, ToCoordinate};
+
+    use super::*;
+
+    fn parent() -> H3Cell {
+        H3Cell::try_from(0x85283473fffffff_u64).unwrap()
+    }
+
+    fn child_map() -> H3CellMap<u32> {
+        parent()
+            .get_children(7)
+            .unwrap()
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (cell, i as u32))
+            .collect()
+    }
+
+    #[test]
+    fn roll_up_aggregations() {
+        let cell_map = child_map();
+        let summed = roll_up(&cell_map, 5, sum).unwrap();
+        assert_eq!(summed.len(), 1);
+        assert_eq!(summed[&parent()], (0..49).sum::<u32>());
+
+        assert_eq!(roll_up(&cell_map, 5, max).unwrap()[&parent()], 48);
+        assert_eq!(roll_up(&cell_map, 5, min).unwrap()[&parent()], 0);
+        assert!((roll_up(&cell_map, 5, mean).unwrap()[&parent()] - 24.0).abs() < 1e-9);
+
+        let counted = roll_up(&cell_map, 6, |values| values.len()).unwrap();
+        assert_eq!(counted.len(), 7);
+        assert!(counted.values().all(|count| *count == 7));
+    }
+
+    #[test]
+    fn roll_up_invalid_resolution() {
+        assert!(roll_up(&child_map(), 8, sum).is_err());
+    }
+
+    #[test]
+    fn roll_up_hexbin_counts() {
+        // the centroid of each child gets binned `position + 1` times
+        let coordinates: Vec<_> = parent()
+            .get_children(7)
+            .unwrap()
+            .iter()
+            .enumerate()
+            .flat_map(|(i, cell)| vec![cell.to_coordinate().unwrap(); i + 1])
+            .collect();
+        let counts = hexbin_count(coordinates, 7).unwrap();
+        assert_eq!(counts.len(), 49);
+
+        let summed = roll_up(&counts, 5, sum).unwrap();
+        assert_eq!(summed[&parent()], (1..=49).sum::<u64>());
+        assert!((roll_up(&counts, 5, mean).unwrap()[&parent()] - 25.0).abs() < 1e-9);
+
+        let divided = drill_down(&summed, 7, split_divide).unwrap();
+        assert!(divided.values().all(|v| (*v - 25.0).abs() < 1e-9));
+    }
+
+    #[test]
+    fn drill_down_split() {
+        let mut cell_map = H3CellMap::default();
+        cell_map.insert(parent(), 49.0_f64);
+
+        let copied = drill_down(&cell_map, 7, split_copy).unwrap();
+        assert_eq!(copied.len(), 49);
+        assert!(copied.values().all(|v| (*v - 49.0).abs() < f64::EPSILON));
+        assert!(copied.keys().all(|cell| cell.resolution() == 7));
+
+        let divided = drill_down(&cell_map, 7, split_divide).unwrap();
+        assert_eq!(divided.len(), 49);
+        assert!(divided.values().all(|v| (*v - 1.0).abs() < 1e-9));
+
+        // roundtrip
+        let summed = roll_up(&divided, 5, sum).unwrap();
+        assert!((summed[&parent()] - 49.0).abs() < 1e-9);
+
+        assert!(drill_down(&cell_map, 4, split_copy).is_err());
+    }
+
+    #[test]
+    fn drill_down_overlapping() {
+        let mut cell_map = H3CellMap::default();
+        cell_map.insert(parent(), 1_u32);
+        cell_map.insert(parent().center_child(6).unwrap(), 2_u32);
+        assert!(matches!(
+            drill_down(&cell_map, 7, split_copy),
+            Err(Error::DuplicateInput)
+        ));
+
+        // same resolution does not overlap
+        cell_map.remove(&parent());
+        assert!(drill_down(&cell_map, 6, split_copy).is_ok());
+    }
+
+    #[test]
+    fn drill_down_pentagon() {
+        let pentagon = H3Cell::try_from(0x8009fffffffffff_u64).unwrap();
+        assert!(pentagon.is_pentagon());
+        let mut cell_map = H3CellMap::default();
+        cell_map.insert(pentagon, 6_u32);
+
+        let divided = drill_down(&cell_map, 1, split_divide).unwrap();
+        assert_eq!(divided.len(), 6);
+        assert!(divided.values().all(|v| (*v - 1.0).abs() < 1e-9));
+    }
+}
@@ -1,4 +1,6 @@
 use std::borrow::Borrow;
+#[cfg(feature = "io")]
+use std::io;
 use std::ops::RangeInclusive;
 
 #[cfg(feature = "use-serde")]
@@ -7,15 +9,18 @@ use serde::{Deserialize, Serialize};
 use crate::collections::indexvec::IndexVec;
 use crate::collections::H3CellSet;
 use crate::collections::HashSet;
+use crate::collections::SortedCellSet;
+#[cfg(feature = "io")]
+use crate::collections::{BlockEncoding, IndexBlock};
 use crate::{compact_cells, Index, H3_MAX_RESOLUTION, H3_MIN_RESOLUTION};
-use crate::{Error, H3Cell};
+use crate::{Error, H3Cell, H3DirectedEdge};
 
 const H3_RESOLUTION_RANGE_USIZE: RangeInclusive<usize> =
     (H3_MIN_RESOLUTION as usize)..=(H3_MAX_RESOLUTION as usize);
 
 /// structure to keep compacted h3ron cells to allow more or less efficient
 /// adding of further cells
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
 #[cfg_attr(feature = "use-serde", derive(Serialize, Deserialize))]
 pub struct CompactedCellVec {
     /// cells by their resolution. The index of the array is the resolution for the referenced vec
@@ -191,6 +196,141 @@ impl<'a> CompactedCellVec {
         }
     }
 
+    /// The outward facing directed edges at the finest resolution contained - all edges leading
+    /// from a covered cell to a cell which is neither contained nor has a contained parent.
+    ///
+    /// In contrast to uncompacting all cells, only the cells along the boundaries of the
+    /// compacted cells get uncompacted, so this is usable for large, mixed resolution
+    /// coverages. The edges are sorted.
+    pub fn boundary_directed_edges(&self) -> Result<Vec<H3DirectedEdge>, Error> {
+        let finest_resolution = match self.finest_resolution_contained() {
+            Some(resolution) => resolution,
+            None => return Ok(Vec::new()),
+        };
+        let coverage = Coverage::new(self);
+
+        let mut boundary_cells = self.cells_by_resolution[finest_resolution as usize].clone();
+        for resolution in H3_MIN_RESOLUTION..finest_resolution {
+            for cell in self.cells_by_resolution[resolution as usize].iter() {
+                // descend along the boundary of the compacted cell. children only
+                // surrounded by covered cells can not contribute to the boundary.
+                let mut candidates = vec![*cell];
+                for child_resolution in (resolution + 1)..=finest_resolution {
+                    let mut next_candidates = Vec::new();
+                    for candidate in candidates.iter() {
+                        for child in candidate.get_children(child_resolution)?.iter() {
+                            if !coverage.is_interior(&child)? {
+                                next_candidates.push(child);
+                            }
+                        }
+                    }
+                    candidates = next_candidates;
+                }
+                boundary_cells.append(&mut candidates);
+            }
+        }
+
+        let mut edges = Vec::new();
+        for cell in boundary_cells {
+            for edge in cell.directed_edges()?.iter() {
+                if !coverage.is_covered(&edge.destination_cell()?)? {
+                    edges.push(edge);
+                }
+            }
+        }
+        edges.sort_unstable();
+        edges.dedup();
+        Ok(edges)
+    }
+
+    /// The union of the cells covered by `self` and `other`. The result is compacted.
+    pub fn union(&self, other: &Self) -> Result<Self, Error> {
+        let cells: Vec<_> = self
+            .iter_compacted_cells()
+            .chain(other.iter_compacted_cells())
+            .collect();
+        Self::try_from(cells)
+    }
+
+    /// The cells covered by both, `self` and `other`. The result is compacted.
+    ///
+    /// This is the union of the cells of `self` covered by `other` and the cells of `other`
+    /// covered by `self`, so no cells need to be uncompacted.
+    pub fn intersection(&self, other: &Self) -> Result<Self, Error> {
+        let mut cells = covered_cells(self, &Coverage::new(other))?;
+        cells.append(&mut covered_cells(other, &Coverage::new(self))?);
+        Self::try_from(cells)
+    }
+
+    /// The cells covered by `self`, but not by `other`. The result is compacted.
+    ///
+    /// Cells of `self` only partially covered by `other` are split into their children
+    /// until the children are either fully covered or not covered at all. Cells are only split
+    /// as far as necessary.
+    pub fn difference(&self, other: &Self) -> Result<Self, Error> {
+        let other_coverage = Coverage::new(other);
+        let other_cells: SortedCellSet = other.iter_compacted_cells().collect();
+
+        let mut cells = Vec::new();
+        let mut candidates: Vec<_> = self.iter_compacted_cells().collect();
+        while let Some(cell) = candidates.pop() {
+            if other_coverage.is_covered(&cell)? {
+                continue;
+            }
+            if other_cells.descendants_in(&cell)?.next().is_some() {
+                // partially covered
+                candidates.extend(cell.get_children(cell.resolution() + 1)?.iter());
+            } else {
+                cells.push(cell);
+            }
+        }
+        Self::try_from(cells)
+    }
+
+    /// Serialize `self` in a compact binary form to `writer`. The cells of each resolution
+    /// are stored as a compressed [`IndexBlock`] using [`BlockEncoding::DeltaVarint`].
+    ///
+    /// This is much more compact than serializing `self` using serde, so it is suited
+    /// for caching large coverages on disk.
+    #[cfg(feature = "io")]
+    pub fn serialize_compressed_into<W: io::Write>(&self, writer: W) -> Result<(), Error> {
+        let blocks: Vec<IndexBlock<H3Cell>> = self
+            .cells_by_resolution
+            .iter()
+            .map(|cells| IndexBlock::with_encoding(cells, BlockEncoding::DeltaVarint))
+            .collect();
+        // the blocks are already compressed
+        crate::io::serialize_into(writer, &blocks, false)
+    }
+
+    /// Deserialize from data written by [`CompactedCellVec::serialize_compressed_into`].
+    ///
+    /// All cells get validated.
+    #[cfg(feature = "io")]
+    pub fn deserialize_compressed_from<R: io::Read>(reader: R) -> Result<Self, Error> {
+        let blocks: Vec<IndexBlock<H3Cell>> = bincode::deserialize_from(reader)?;
+        if blocks.len() != H3_RESOLUTION_RANGE_USIZE.end() + 1 {
+            return Err(Error::DecompressionError(format!(
+                "expected {} blocks, found {}",
+                H3_RESOLUTION_RANGE_USIZE.end() + 1,
+                blocks.len()
+            )));
+        }
+
+        let mut cv = Self::new();
+        for (resolution, block) in blocks.iter().enumerate() {
+            let cells = &mut cv.cells_by_resolution[resolution];
+            for cell in block.iter_uncompressed()? {
+                let cell = H3Cell::try_from(cell.h3index())?;
+                if cell.resolution() as usize != resolution {
+                    return Err(Error::ResMismatch);
+                }
+                cells.push(cell);
+            }
+        }
+        Ok(cv)
+    }
+
     /// deduplicate the internal cell vectors
     pub fn dedup(&mut self) -> Result<(), Error> {
         self.cells_by_resolution.iter_mut().for_each(|cells| {
@@ -372,6 +512,69 @@ impl<'a> Iterator for CompactedCellVecUncompactedIterator<'a> {
     }
 }
 
+/// the cells of `compacted_vec` covered by `coverage`
+fn covered_cells(
+    compacted_vec: &CompactedCellVec,
+    coverage: &Coverage,
+) -> Result<Vec<H3Cell>, Error> {
+    let mut cells = Vec::new();
+    for cell in compacted_vec.iter_compacted_cells() {
+        if coverage.is_covered(&cell)? {
+            cells.push(cell);
+        }
+    }
+    Ok(cells)
+}
+
+/// lookup of the cells covered by a `CompactedCellVec`
+struct Coverage {
+    cells: H3CellSet,
+    resolutions: Vec<u8>,
+}
+
+impl Coverage {
+    fn new(compacted_vec: &CompactedCellVec) -> Self {
+        let mut cells = H3CellSet::default();
+        let mut resolutions = Vec::new();
+        for (resolution, resolution_cells) in compacted_vec.cells_by_resolution.iter().enumerate() {
+            if !resolution_cells.is_empty() {
+                cells.extend(resolution_cells.iter().copied());
+                resolutions.push(resolution as u8);
+            }
+        }
+        Self { cells, resolutions }
+    }
+
+    /// check if `cell` or one of its parents is contained
+    fn is_covered(&self, cell: &H3Cell) -> Result<bool, Error> {
+        let cell_resolution = cell.resolution();
+        for resolution in self.resolutions.iter() {
+            if *resolution > cell_resolution {
+                break;
+            }
+            let parent = if *resolution == cell_resolution {
+                *cell
+            } else {
+                cell.get_parent(*resolution)?
+            };
+            if self.cells.contains(&parent) {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// check if `cell` and all of its neighbors are covered
+    fn is_interior(&self, cell: &H3Cell) -> Result<bool, Error> {
+        for neighbor in cell.grid_disk(1)?.iter() {
+            if !self.is_covered(&neighbor)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::convert::TryInto;
@@ -379,7 +582,9 @@ mod tests {
     #[cfg(feature = "use-serde")]
     use bincode::{deserialize, serialize};
 
-    use crate::collections::CompactedCellVec;
+    use crate::collections::{CompactedCellVec, H3CellSet};
+    use crate::topology::boundary_directed_edges;
+    use crate::H3Cell;
 
     #[test]
     fn compactedvec_is_empty() {
@@ -403,4 +608,143 @@ mod tests {
         let cv_2: CompactedCellVec = deserialize(&serialized_data).unwrap();
         assert_eq!(cv, cv_2);
     }
+
+    fn uncompacted(cv: &CompactedCellVec, resolution: u8) -> H3CellSet {
+        cv.iter_uncompacted_cells(resolution)
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn set_operations_coarse_and_fine() {
+        let coarse: H3Cell = 0x85283473fffffff_u64.try_into().unwrap();
+        let fine = coarse.center_child(7).unwrap();
+        let cv_coarse = CompactedCellVec::try_from(vec![coarse]).unwrap();
+        let cv_fine = CompactedCellVec::try_from(vec![fine]).unwrap();
+
+        // only the parents of the fine cell get split
+        let difference = cv_coarse.difference(&cv_fine).unwrap();
+        assert_eq!(difference.len(), 12);
+        assert_eq!(difference.len_resolutions()[6], 6);
+        assert_eq!(difference.len_resolutions()[7], 6);
+        assert_eq!(uncompacted(&difference, 7).len(), 48);
+        assert!(!uncompacted(&difference, 7).contains(&fine));
+
+        assert!(cv_fine.difference(&cv_coarse).unwrap().is_empty());
+        assert_eq!(cv_coarse.intersection(&cv_fine).unwrap(), cv_fine);
+        assert_eq!(cv_fine.intersection(&cv_coarse).unwrap(), cv_fine);
+        assert_eq!(cv_coarse.union(&cv_fine).unwrap(), cv_coarse);
+
+        // recompacted
+        assert_eq!(difference.union(&cv_fine).unwrap(), cv_coarse);
+    }
+
+    #[test]
+    fn set_operations_match_uncompacted() {
+        let cell: H3Cell = 0x85283473fffffff_u64.try_into().unwrap();
+        let cv_a =
+            CompactedCellVec::try_from(cell.grid_disk(1).unwrap().iter().collect::<Vec<_>>())
+                .unwrap();
+        let neighbor = cell.grid_ring_unsafe(1).unwrap().first().unwrap();
+        let cells_b: Vec<_> = neighbor
+            .center_child(7)
+            .unwrap()
+            .grid_disk(6)
+            .unwrap()
+            .iter()
+            .collect();
+        let cv_b = CompactedCellVec::try_from(cells_b).unwrap();
+        assert!(cv_b.len_resolutions()[6] > 0);
+
+        let a = uncompacted(&cv_a, 7);
+        let b = uncompacted(&cv_b, 7);
+        assert_eq!(
+            uncompacted(&cv_a.union(&cv_b).unwrap(), 7),
+            a.union(&b).copied().collect()
+        );
+        assert_eq!(
+            uncompacted(&cv_a.intersection(&cv_b).unwrap(), 7),
+            a.intersection(&b).copied().collect()
+        );
+        assert_eq!(
+            uncompacted(&cv_a.difference(&cv_b).unwrap(), 7),
+            a.difference(&b).copied().collect()
+        );
+        assert_eq!(
+            uncompacted(&cv_b.difference(&cv_a).unwrap(), 7),
+            b.difference(&a).copied().collect()
+        );
+    }
+
+    #[cfg(feature = "io")]
+    #[test]
+    fn compactedvec_compressed_roundtrip() {
+        let cell: H3Cell = 0x85283473fffffff_u64.try_into().unwrap();
+        let mut cells: Vec<_> = cell.grid_disk(2).unwrap().iter().collect();
+        cells.extend(
+            cell.grid_ring_unsafe(3)
+                .unwrap()
+                .first()
+                .unwrap()
+                .get_children(8)
+                .unwrap()
+                .iter()
+                .skip(1),
+        );
+        let cv = CompactedCellVec::try_from(cells).unwrap();
+
+        let mut data = Vec::new();
+        cv.serialize_compressed_into(&mut data).unwrap();
+        let cv_2 = CompactedCellVec::deserialize_compressed_from(data.as_slice()).unwrap();
+        assert_eq!(cv, cv_2);
+
+        assert!(CompactedCellVec::deserialize_compressed_from(&data[..data.len() / 2]).is_err());
+    }
+
+    #[cfg(feature = "io")]
+    #[test]
+    fn compactedvec_compressed_invalid_cell() {
+        use crate::collections::IndexBlock;
+        use crate::{Index, H3_MAX_RESOLUTION};
+
+        // the unused digit of resolution 15 is not set to 7
+        let invalid_cell = H3Cell::new(0x85283473ffffffe_u64);
+        assert!(!invalid_cell.is_valid());
+        assert_eq!(invalid_cell.resolution(), 5);
+
+        let blocks: Vec<IndexBlock<H3Cell>> = (0..=H3_MAX_RESOLUTION)
+            .map(|resolution| {
+                if resolution == 5 {
+                    IndexBlock::from([invalid_cell].as_slice())
+                } else {
+                    IndexBlock::from([].as_slice())
+                }
+            })
+            .collect();
+        let mut data = Vec::new();
+        crate::io::serialize_into(&mut data, &blocks, false).unwrap();
+        assert!(CompactedCellVec::deserialize_compressed_from(data.as_slice()).is_err());
+    }
+
+    #[test]
+    fn boundary_directed_edges_mixed_resolutions() {
+        let cell: H3Cell = 0x85283473fffffff_u64.try_into().unwrap();
+        let mut cells: Vec<_> = cell.grid_disk(1).unwrap().iter().collect();
+        cells.extend(cell.grid_ring_unsafe(2).unwrap().iter().take(3));
+        let fine_cell = cell.grid_ring_unsafe(3).unwrap().first().unwrap();
+        cells.extend(fine_cell.get_children(8).unwrap().iter().skip(1));
+        let mut cv = CompactedCellVec::new();
+        cv.add_cells(cells, true).unwrap();
+        assert!(cv.len_resolutions()[5] > 0);
+        assert!(cv.len_resolutions()[8] > 0);
+
+        let uncompacted: H3CellSet = cv
+            .iter_uncompacted_cells(8)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            cv.boundary_directed_edges().unwrap(),
+            boundary_directed_edges(&uncompacted).unwrap()
+        );
+    }
 }
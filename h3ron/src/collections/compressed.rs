@@ -1,13 +1,64 @@
 use std::borrow::Borrow;
 use std::marker::PhantomData;
 use std::mem::size_of;
+use std::ops::Range;
 
 use lz4_flex::{compress, decompress_into};
-#[cfg(feature = "use-serde")]
+#[cfg(any(feature = "use-serde", feature = "io"))]
 use serde::{Deserialize, Serialize};
 
 use crate::{Error, Index, IndexVec};
 
+/// magic bytes at the start of the data of chunked blocks.
+const BLOCK_MAGIC: &[u8; 4] = b"h3ib";
+
+/// maximum number of bytes of a varint-encoded `u64`.
+const MAX_VARINT_LEN: usize = 10;
+
+/// maximum number of bytes lz4 decompresses a single byte of compressed data to.
+#[cfg(any(feature = "use-serde", feature = "io"))]
+const MAX_LZ4_EXPANSION: usize = 255;
+
+/// number of h3indexes in each chunk of a block.
+const CHUNK_LEN: usize = 1024;
+
+/// magic, encoding (u8), chunk length (u32), number of chunks (u32)
+const HEADER_SIZE: usize = BLOCK_MAGIC.len() + 1 + 4 + 4;
+
+/// end offset of the chunk data (u64), first h3index of the chunk (u64)
+const CHUNK_ENTRY_SIZE: usize = 8 + 8;
+
+/// The encoding of the indexes within an [`IndexBlock`]. The encoding is recorded in the
+/// header of the block, so reading a block detects it automatically.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[repr(u8)]
+pub enum BlockEncoding {
+    /// The bytes of the indexes are grouped by their position in the `u64`. Suitable for
+    /// all blocks and keeps the order of the indexes cheap to decode.
+    #[default]
+    ByteColumns = 0,
+
+    /// The differences between successive indexes are stored as variable-length integers.
+    /// Much more compact for sorted indexes - for example from a [`H3Treemap`](crate::collections::H3Treemap)
+    /// or from [`compact_cells`](crate::compact_cells) - but works with unsorted indexes as well.
+    DeltaVarint = 1,
+}
+
+impl TryFrom<u8> for BlockEncoding {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::ByteColumns),
+            1 => Ok(Self::DeltaVarint),
+            _ => Err(Error::DecompressionError(format!(
+                "unsupported block encoding {}",
+                value
+            ))),
+        }
+    }
+}
+
 /// `IndexVec` allows to store h3index in compressed form.
 ///
 /// The main purpose of this is to allow having seldom used data in memory without
@@ -22,11 +73,22 @@ use crate::{Error, Index, IndexVec};
 /// h3index. For spatially close h3index this results in a quite good compression ratio as many
 /// bytes are common over many h3indexes. As an example: a k-ring with `k=50` and 7651 cells
 /// compresses from 61kb to around 7.6kb.
+///
+/// The h3indexes are split into chunks which get compressed independently. A table of
+/// the chunk offsets allows random access using [`IndexBlock::get`], [`IndexBlock::iter_range`]
+/// and [`IndexBlock::binary_search`] by only decompressing the chunks involved.
 #[derive(Clone, PartialEq, Debug)]
-#[cfg_attr(feature = "use-serde", derive(Serialize, Deserialize))]
 #[cfg_attr(
-    feature = "use-serde",
-    serde(bound(serialize = "T: Serialize", deserialize = "T: Deserialize<'de>",))
+    any(feature = "use-serde", feature = "io"),
+    derive(Serialize, Deserialize)
+)]
+// `T` is only a marker, the indexes are always stored as `u64`
+#[cfg_attr(
+    any(feature = "use-serde", feature = "io"),
+    serde(
+        try_from = "RawIndexBlock",
+        bound(serialize = "", deserialize = "T: Index")
+    )
 )]
 pub struct IndexBlock<T> {
     num_indexes: usize,
@@ -62,6 +124,19 @@ where
         size_of::<Self>() + size_of::<u8>() * self.len()
     }
 
+    /// Create a block using the given `encoding`.
+    ///
+    /// Converting from slices, `Vec`s and iterators uses [`BlockEncoding::ByteColumns`].
+    pub fn with_encoding(index_slice: &[T], encoding: BlockEncoding) -> Self {
+        let h3indexes: Vec<_> = index_slice.iter().map(Index::h3index).collect();
+        Self::from_h3indexes(&h3indexes, index_slice.len() >= 4, encoding)
+    }
+
+    /// The encoding of the indexes in this block.
+    pub fn encoding(&self) -> Result<BlockEncoding, Error> {
+        Ok(self.layout()?.encoding)
+    }
+
     /// returns an iterator over the decompressed decompressed contents of the `IndexBlock`.
     ///
     /// Useful for situations where only one or few decompressions are done. When
@@ -71,42 +146,449 @@ where
         let decompressor = Decompressor::default();
         decompressor.decompress_block_owning(self)
     }
+
+    /// Get the index at position `pos`. Only the chunk containing `pos` gets decompressed.
+    pub fn get(&self, pos: usize) -> Result<Option<T>, Error> {
+        if pos >= self.num_indexes {
+            return Ok(None);
+        }
+        let layout = self.layout()?;
+        let chunk_i = pos / layout.chunk_len;
+        let mut h3indexes = Vec::with_capacity(layout.chunk_num_indexes(chunk_i));
+        layout.decode_chunk_into(chunk_i, &mut h3indexes)?;
+        Ok(h3indexes
+            .get(pos - chunk_i * layout.chunk_len)
+            .map(|h3index| T::from_h3index(*h3index)))
+    }
+
+    /// Iterate over the indexes at the positions of `range`. Only the chunks overlapping
+    /// `range` get decompressed. The range is truncated to the length of the block.
+    pub fn iter_range(&self, range: Range<usize>) -> Result<std::vec::IntoIter<T>, Error> {
+        let end = range.end.min(self.num_indexes);
+        if range.start >= end {
+            return Ok(Vec::new().into_iter());
+        }
+        let layout = self.layout()?;
+        let first_chunk_i = range.start / layout.chunk_len;
+        let mut h3indexes = Vec::with_capacity(end - first_chunk_i * layout.chunk_len);
+        for chunk_i in first_chunk_i..=((end - 1) / layout.chunk_len) {
+            layout.decode_chunk_into(chunk_i, &mut h3indexes)?;
+        }
+        let offset = first_chunk_i * layout.chunk_len;
+        Ok(h3indexes[(range.start - offset)..(end - offset)]
+            .iter()
+            .map(|h3index| T::from_h3index(*h3index))
+            .collect::<Vec<_>>()
+            .into_iter())
+    }
+
+    /// Binary search for `index` in a block containing sorted indexes, with the same semantics
+    /// as [`slice::binary_search`].
+    ///
+    /// The chunk possibly containing `index` is found using the chunk table, so only this
+    /// chunk gets decompressed. The result is meaningless when the block is not sorted.
+    pub fn binary_search(&self, index: &T) -> Result<Result<usize, usize>, Error> {
+        if self.is_empty() {
+            return Ok(Err(0));
+        }
+        let h3index = index.h3index();
+        let layout = self.layout()?;
+
+        // the last chunk starting with an h3index <= the searched one
+        let chunk_i = match partition_point(layout.num_chunks, |chunk_i| {
+            layout.chunk_first(chunk_i) <= h3index
+        }) {
+            0 => return Ok(Err(0)),
+            num_chunks => num_chunks - 1,
+        };
+
+        let mut h3indexes = Vec::with_capacity(layout.chunk_num_indexes(chunk_i));
+        layout.decode_chunk_into(chunk_i, &mut h3indexes)?;
+        let offset = chunk_i * layout.chunk_len;
+        Ok(match h3indexes.binary_search(&h3index) {
+            Ok(pos) => Ok(pos + offset),
+            Err(pos) => Err(pos + offset),
+        })
+    }
+
+    fn layout(&self) -> Result<BlockLayout, Error> {
+        BlockLayout::parse(&self.block_data, self.num_indexes, self.compressed)
+    }
 }
 
-impl<'a, T> From<&[T]> for IndexBlock<T>
-where
-    T: Index,
-{
-    fn from(index_slice: &[T]) -> Self {
-        let byte_offset = index_slice.len();
-        let mut buf = vec![255u8; index_slice.len() * (size_of::<u64>() / size_of::<u8>())];
+impl<T> IndexBlock<T> {
+    fn from_h3indexes(h3indexes: &[u64], compressed: bool, encoding: BlockEncoding) -> Self {
+        let num_chunks = num_chunks(h3indexes.len(), CHUNK_LEN);
+        let mut block_data = Vec::with_capacity(HEADER_SIZE + num_chunks * CHUNK_ENTRY_SIZE);
+        block_data.extend_from_slice(BLOCK_MAGIC);
+        block_data.push(encoding as u8);
+        block_data.extend_from_slice(&(CHUNK_LEN as u32).to_le_bytes());
+        block_data.extend_from_slice(&(num_chunks as u32).to_le_bytes());
+
+        let table_start = block_data.len();
+        let data_start = table_start + num_chunks * CHUNK_ENTRY_SIZE;
+        block_data.resize(data_start, 0);
 
-        for (pos, index) in index_slice.iter().enumerate() {
-            let h3index = index.h3index() as u64;
+        for (chunk_i, chunk) in h3indexes.chunks(CHUNK_LEN).enumerate() {
+            block_data.extend_from_slice(&encode_chunk(chunk, compressed, encoding));
+            let entry_start = table_start + chunk_i * CHUNK_ENTRY_SIZE;
+            let chunk_end = (block_data.len() - data_start) as u64;
+            block_data[entry_start..(entry_start + 8)].copy_from_slice(&chunk_end.to_le_bytes());
+            block_data[(entry_start + 8)..(entry_start + 16)]
+                .copy_from_slice(&chunk[0].to_le_bytes());
+        }
 
+        Self {
+            num_indexes: h3indexes.len(),
+            compressed,
+            block_data,
+            phantom_data: PhantomData,
+        }
+    }
+}
+
+fn encode_chunk(h3indexes: &[u64], compressed: bool, encoding: BlockEncoding) -> Vec<u8> {
+    match encoding {
+        BlockEncoding::ByteColumns => {
             // keep the same bits of the h3indexes together to improve compression
             // when the h3indexes are closely together.
-            let h3index_bytes = h3index.to_le_bytes();
-            buf[pos] = h3index_bytes[0];
-            buf[pos + byte_offset] = h3index_bytes[1];
-            buf[pos + (2 * byte_offset)] = h3index_bytes[2];
-            buf[pos + (3 * byte_offset)] = h3index_bytes[3];
-            buf[pos + (4 * byte_offset)] = h3index_bytes[4];
-            buf[pos + (5 * byte_offset)] = h3index_bytes[5];
-            buf[pos + (6 * byte_offset)] = h3index_bytes[6];
-            buf[pos + (7 * byte_offset)] = h3index_bytes[7];
+            let byte_offset = h3indexes.len();
+            let mut buf = vec![255u8; h3indexes.len() * (size_of::<u64>() / size_of::<u8>())];
+            for (pos, h3index) in h3indexes.iter().enumerate() {
+                for (byte_i, byte) in h3index.to_le_bytes().iter().enumerate() {
+                    buf[pos + (byte_i * byte_offset)] = *byte;
+                }
+            }
+            if compressed {
+                compress(&buf)
+            } else {
+                buf
+            }
         }
+        BlockEncoding::DeltaVarint => {
+            let mut buf = Vec::with_capacity(h3indexes.len() * 2);
+            let mut previous = 0_u64;
+            for h3index in h3indexes.iter() {
+                write_varint(
+                    &mut buf,
+                    zigzag_encode(h3index.wrapping_sub(previous) as i64),
+                );
+                previous = *h3index;
+            }
+            if compressed {
+                // the size of the varints is not known in advance
+                let mut chunk_data = (buf.len() as u32).to_le_bytes().to_vec();
+                chunk_data.extend_from_slice(&compress(&buf));
+                chunk_data
+            } else {
+                buf
+            }
+        }
+    }
+}
 
-        let compressed = index_slice.len() >= 4;
-        let block_data = if compressed { compress(&buf) } else { buf };
+/// view on the header, the chunk table and the chunk data of the data of a block
+struct BlockLayout<'a> {
+    num_indexes: usize,
+    compressed: bool,
+    encoding: BlockEncoding,
+    chunk_len: usize,
+    num_chunks: usize,
+    chunk_table: &'a [u8],
+    chunk_data: &'a [u8],
+}
 
-        Self {
-            num_indexes: index_slice.len(),
+impl<'a> BlockLayout<'a> {
+    fn parse(block_data: &'a [u8], num_indexes: usize, compressed: bool) -> Result<Self, Error> {
+        let invalid = |msg: &str| Error::DecompressionError(format!("invalid block: {}", msg));
+        if block_data.len() < HEADER_SIZE || !block_data.starts_with(BLOCK_MAGIC) {
+            return Err(invalid("missing header"));
+        }
+        let encoding = BlockEncoding::try_from(block_data[4])?;
+        let chunk_len = read_u32(block_data, 5) as usize;
+        let num_chunks = read_u32(block_data, 9) as usize;
+        if chunk_len == 0 || chunk_len > CHUNK_LEN {
+            return Err(invalid("unsupported chunk length"));
+        }
+        if num_chunks != self::num_chunks(num_indexes, chunk_len) {
+            return Err(invalid(
+                "number of chunks does not match the number of indexes",
+            ));
+        }
+        let data_start = HEADER_SIZE + num_chunks * CHUNK_ENTRY_SIZE;
+        if block_data.len() < data_start {
+            return Err(invalid("truncated chunk table"));
+        }
+        Ok(Self {
+            num_indexes,
             compressed,
-            block_data,
-            phantom_data: PhantomData::default(),
+            encoding,
+            chunk_len,
+            num_chunks,
+            chunk_table: &block_data[HEADER_SIZE..data_start],
+            chunk_data: &block_data[data_start..],
+        })
+    }
+
+    fn chunk_end(&self, chunk_i: usize) -> usize {
+        read_u64(self.chunk_table, chunk_i * CHUNK_ENTRY_SIZE) as usize
+    }
+
+    fn chunk_first(&self, chunk_i: usize) -> u64 {
+        read_u64(self.chunk_table, chunk_i * CHUNK_ENTRY_SIZE + 8)
+    }
+
+    fn chunk_num_indexes(&self, chunk_i: usize) -> usize {
+        self.chunk_len
+            .min(self.num_indexes.saturating_sub(chunk_i * self.chunk_len))
+    }
+
+    /// decode the chunk `chunk_i` and append its h3indexes to `out`
+    fn decode_chunk_into(&self, chunk_i: usize, out: &mut Vec<u64>) -> Result<(), Error> {
+        let start = if chunk_i == 0 {
+            0
+        } else {
+            self.chunk_end(chunk_i - 1)
+        };
+        let end = self.chunk_end(chunk_i);
+        if start > end || end > self.chunk_data.len() {
+            return Err(Error::DecompressionError(format!(
+                "invalid block: chunk {} out of bounds",
+                chunk_i
+            )));
+        }
+        let data = &self.chunk_data[start..end];
+        let num_indexes = self.chunk_num_indexes(chunk_i);
+
+        match self.encoding {
+            BlockEncoding::ByteColumns => {
+                let uncompressed_size = num_indexes * size_of::<u64>();
+                let decompressed;
+                let buf = if self.compressed {
+                    decompressed = decompress_chunk(data, uncompressed_size)?;
+                    decompressed.as_slice()
+                } else if data.len() == uncompressed_size {
+                    data
+                } else {
+                    return Err(size_mismatch(uncompressed_size, data.len()));
+                };
+                out.extend(
+                    (0..num_indexes).map(|pos| h3index_from_block_buf(buf, pos, num_indexes)),
+                );
+            }
+            BlockEncoding::DeltaVarint => {
+                let decompressed;
+                let buf = if self.compressed {
+                    if data.len() < 4 {
+                        return Err(size_mismatch(4, data.len()));
+                    }
+                    let uncompressed_size = read_u32(data, 0) as usize;
+                    if uncompressed_size > num_indexes * MAX_VARINT_LEN {
+                        return Err(size_mismatch(
+                            num_indexes * MAX_VARINT_LEN,
+                            uncompressed_size,
+                        ));
+                    }
+                    decompressed = decompress_chunk(&data[4..], uncompressed_size)?;
+                    decompressed.as_slice()
+                } else {
+                    data
+                };
+                let mut pos = 0;
+                let mut previous = 0_u64;
+                for _ in 0..num_indexes {
+                    let (value, value_len) = read_varint(&buf[pos..])?;
+                    pos += value_len;
+                    previous = previous.wrapping_add(zigzag_decode(value) as u64);
+                    out.push(previous);
+                }
+                if pos != buf.len() {
+                    return Err(size_mismatch(pos, buf.len()));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// decode all chunks and append the h3indexes to `out`
+    fn decode_into(&self, out: &mut Vec<u64>) -> Result<(), Error> {
+        out.reserve(self.num_indexes);
+        for chunk_i in 0..self.num_chunks {
+            self.decode_chunk_into(chunk_i, out)?;
+        }
+        Ok(())
+    }
+}
+
+fn decompress_chunk(data: &[u8], uncompressed_size: usize) -> Result<Vec<u8>, Error> {
+    let mut buf = vec![0xff; uncompressed_size];
+    let bytes_uncompressed =
+        decompress_into(data, &mut buf).map_err(|e| Error::DecompressionError(e.to_string()))?;
+    if bytes_uncompressed != uncompressed_size {
+        return Err(size_mismatch(uncompressed_size, bytes_uncompressed));
+    }
+    Ok(buf)
+}
+
+fn size_mismatch(expected: usize, found: usize) -> Error {
+    Error::DecompressionError(format!(
+        "size missmatch. expected {} bytes, found {} bytes",
+        expected, found
+    ))
+}
+
+const fn zigzag_encode(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+const fn zigzag_decode(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+/// append `value` as LEB128 varint to `buf`
+fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+/// read a LEB128 varint from the start of `buf`. Returns the value and the number of
+/// bytes read.
+fn read_varint(buf: &[u8]) -> Result<(u64, usize), Error> {
+    let mut value = 0_u64;
+    for (i, byte) in buf.iter().take(MAX_VARINT_LEN).enumerate() {
+        value |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok((value, i + 1));
+        }
+    }
+    Err(Error::DecompressionError("invalid varint".to_string()))
+}
+
+/// the number of chunks required to store `num_indexes`
+const fn num_chunks(num_indexes: usize, chunk_len: usize) -> usize {
+    if num_indexes == 0 {
+        0
+    } else {
+        (num_indexes - 1) / chunk_len + 1
+    }
+}
+
+/// the number of leading elements in `0..len` for which `pred` returns true. `pred` must
+/// be true for a prefix of `0..len` only.
+fn partition_point<P>(len: usize, pred: P) -> usize
+where
+    P: Fn(usize) -> bool,
+{
+    let (mut low, mut high) = (0, len);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if pred(mid) {
+            low = mid + 1;
+        } else {
+            high = mid;
         }
     }
+    low
+}
+
+fn read_u32(buf: &[u8], pos: usize) -> u32 {
+    let mut bytes = [0u8; 4];
+    bytes.copy_from_slice(&buf[pos..(pos + 4)]);
+    u32::from_le_bytes(bytes)
+}
+
+fn read_u64(buf: &[u8], pos: usize) -> u64 {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&buf[pos..(pos + 8)]);
+    u64::from_le_bytes(bytes)
+}
+
+/// The serialized form of [`IndexBlock`]. Used to validate deserialized blocks and
+/// to read blocks serialized by earlier versions without chunks.
+#[cfg(any(feature = "use-serde", feature = "io"))]
+#[derive(Deserialize)]
+struct RawIndexBlock {
+    num_indexes: usize,
+    compressed: bool,
+    block_data: Vec<u8>,
+    #[allow(dead_code)]
+    phantom_data: PhantomData<()>,
+}
+
+#[cfg(any(feature = "use-serde", feature = "io"))]
+impl<T> TryFrom<RawIndexBlock> for IndexBlock<T>
+where
+    T: Index,
+{
+    type Error = Error;
+
+    fn try_from(raw: RawIndexBlock) -> Result<Self, Self::Error> {
+        if raw.block_data.starts_with(BLOCK_MAGIC) {
+            let block = Self {
+                num_indexes: raw.num_indexes,
+                compressed: raw.compressed,
+                block_data: raw.block_data,
+                phantom_data: PhantomData,
+            };
+            let layout = block.layout()?;
+            let mut previous_end = 0;
+            for chunk_i in 0..layout.num_chunks {
+                let end = layout.chunk_end(chunk_i);
+                if end < previous_end || end > layout.chunk_data.len() {
+                    return Err(Error::DecompressionError(
+                        "invalid block: chunk out of bounds".to_string(),
+                    ));
+                }
+                previous_end = end;
+            }
+            Ok(block)
+        } else {
+            // a single chunk without any header as written by earlier versions
+            let size_mismatch =
+                || Error::DecompressionError("size missmatch in block without header".to_string());
+            let uncompressed_size = raw
+                .num_indexes
+                .checked_mul(size_of::<u64>())
+                .ok_or_else(size_mismatch)?;
+            let buf = if raw.compressed {
+                // check the size before allocating the buffer for the decompressed data
+                if uncompressed_size > raw.block_data.len().saturating_mul(MAX_LZ4_EXPANSION) {
+                    return Err(size_mismatch());
+                }
+                let mut buf = vec![0xff; uncompressed_size];
+                let bytes_uncompressed = decompress_into(&raw.block_data, &mut buf)
+                    .map_err(|e| Error::DecompressionError(e.to_string()))?;
+                if bytes_uncompressed != uncompressed_size {
+                    return Err(size_mismatch());
+                }
+                buf
+            } else if raw.block_data.len() == uncompressed_size {
+                raw.block_data
+            } else {
+                return Err(size_mismatch());
+            };
+            let h3indexes: Vec<_> = (0..raw.num_indexes)
+                .map(|pos| h3index_from_block_buf(&buf, pos, raw.num_indexes))
+                .collect();
+            Ok(Self::from_h3indexes(
+                &h3indexes,
+                raw.compressed,
+                BlockEncoding::ByteColumns,
+            ))
+        }
+    }
+}
+
+impl<T> From<&[T]> for IndexBlock<T>
+where
+    T: Index,
+{
+    fn from(index_slice: &[T]) -> Self {
+        Self::with_encoding(index_slice, BlockEncoding::ByteColumns)
+    }
 }
 
 impl<T> From<Vec<T>> for IndexBlock<T>
@@ -145,7 +627,7 @@ where
 }
 
 pub struct Decompressor {
-    buf: Vec<u8>,
+    buf: Vec<u64>,
 }
 
 impl Decompressor {
@@ -153,26 +635,17 @@ impl Decompressor {
         Self { buf: vec![] }
     }
 
-    fn decompress_block_into_inner_buf<T>(&mut self, block: &IndexBlock<T>) -> Result<bool, Error>
+    fn decompress_block_into_inner_buf<T>(&mut self, block: &IndexBlock<T>) -> Result<(), Error>
     where
         T: Index,
     {
-        if block.is_compressed() {
-            let uncompressed_size = block.num_indexes * size_of::<u64>();
-            self.buf.resize(uncompressed_size, 0xff);
-            let bytes_uncompressed = decompress_into(block.block_data.as_slice(), &mut self.buf)
-                .map_err(|e| Error::DecompressionError(e.to_string()))?;
-            if bytes_uncompressed != uncompressed_size {
-                return Err(Error::DecompressionError(format!(
-                    "size missmatch. expected {} bytes, uncompressed to {} bytes",
-                    uncompressed_size, bytes_uncompressed
-                )));
-            }
-            Ok(true)
-        } else {
-            Ok(false)
+        self.buf.clear();
+        if !block.is_empty() {
+            block.layout()?.decode_into(&mut self.buf)?;
         }
+        Ok(())
     }
+
     pub fn decompress_block<'a, 'b, T>(
         &'a mut self,
         block: &'b IndexBlock<T>,
@@ -180,12 +653,11 @@ impl Decompressor {
     where
         T: Index,
     {
-        let buf = if self.decompress_block_into_inner_buf(block)? {
-            Some(self.buf.as_slice())
-        } else {
-            None
-        };
-        Ok(DecompressedIter { buf, block, pos: 0 })
+        self.decompress_block_into_inner_buf(block)?;
+        Ok(DecompressedIter {
+            inner_iter: self.buf.iter(),
+            phantom_data: PhantomData,
+        })
     }
 
     pub fn decompress_block_owning<T>(
@@ -195,12 +667,11 @@ impl Decompressor {
     where
         T: Index,
     {
-        let buf = if self.decompress_block_into_inner_buf(block)? {
-            Some(self.buf)
-        } else {
-            None
-        };
-        Ok(OwningDecompressedIter { buf, block, pos: 0 })
+        self.decompress_block_into_inner_buf(block)?;
+        Ok(OwningDecompressedIter {
+            inner_iter: self.buf.into_iter(),
+            phantom_data: PhantomData,
+        })
     }
 }
 
@@ -227,9 +698,8 @@ fn h3index_from_block_buf(buf: &[u8], pos: usize, num_indexes: usize) -> u64 {
 }
 
 pub struct DecompressedIter<'a, 'b, T> {
-    buf: Option<&'a [u8]>,
-    block: &'b IndexBlock<T>,
-    pos: usize,
+    inner_iter: std::slice::Iter<'a, u64>,
+    phantom_data: PhantomData<&'b IndexBlock<T>>,
 }
 
 impl<'a, 'b, T> Iterator for DecompressedIter<'a, 'b, T>
@@ -239,20 +709,13 @@ where
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.pos >= self.block.num_indexes {
-            return None;
-        }
-        let buf = match self.buf {
-            Some(b) => b,
-            None => self.block.block_data.as_slice(),
-        };
-        let h3index: u64 = h3index_from_block_buf(buf, self.pos, self.block.num_indexes);
-        self.pos += 1;
-        Some(T::from_h3index(h3index))
+        self.inner_iter
+            .next()
+            .map(|h3index| T::from_h3index(*h3index))
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.block.num_indexes.saturating_sub(self.pos), None)
+        self.inner_iter.size_hint()
     }
 }
 
@@ -262,9 +725,8 @@ where
 /// many blocks are decompressed using the `Decompressor` is more efficient as the
 /// decompression buffer needs to be allocated only once.
 pub struct OwningDecompressedIter<'a, T> {
-    buf: Option<Vec<u8>>,
-    block: &'a IndexBlock<T>,
-    pos: usize,
+    inner_iter: std::vec::IntoIter<u64>,
+    phantom_data: PhantomData<&'a IndexBlock<T>>,
 }
 
 impl<'a, T> Iterator for OwningDecompressedIter<'a, T>
@@ -274,29 +736,21 @@ where
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.pos >= self.block.num_indexes {
-            return None;
-        }
-        let buf = match self.buf.as_ref() {
-            Some(b) => b.as_slice(),
-            None => self.block.block_data.as_slice(),
-        };
-        let h3index: u64 = h3index_from_block_buf(buf, self.pos, self.block.num_indexes);
-        self.pos += 1;
-        Some(T::from_h3index(h3index))
+        self.inner_iter.next().map(T::from_h3index)
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.block.num_indexes.saturating_sub(self.pos), None)
+        self.inner_iter.size_hint()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::collections::compressed::Decompressor;
-    use crate::H3Cell;
+    use crate::{H3Cell, Index};
 
-    use super::IndexBlock;
+    use super::{read_varint, write_varint, zigzag_decode, zigzag_encode};
+    use super::{BlockEncoding, IndexBlock};
 
     fn make_grid_disk(k: u32) -> Vec<H3Cell> {
         let idx = H3Cell::try_from(0x89283080ddbffff_u64).unwrap();
@@ -363,6 +817,221 @@ mod tests {
         assert_eq!(ring.len(), ib.iter_uncompressed().unwrap().count());
     }
 
+    #[test]
+    fn test_indexblock_random_access() {
+        let cells = make_grid_disk(50);
+        assert!(cells.len() > 3 * super::CHUNK_LEN);
+        let ib = IndexBlock::from(cells.as_slice());
+
+        for pos in [0, 1, 1023, 1024, 1025, 4000, cells.len() - 1] {
+            assert_eq!(ib.get(pos).unwrap(), Some(cells[pos]));
+        }
+        assert_eq!(ib.get(cells.len()).unwrap(), None);
+
+        for range in [0..10, 1000..3000, 1024..2048, 5000..cells.len()] {
+            assert_eq!(
+                ib.iter_range(range.clone()).unwrap().collect::<Vec<_>>(),
+                cells[range].to_vec()
+            );
+        }
+        assert_eq!(
+            ib.iter_range(7000..20000).unwrap().count(),
+            cells.len() - 7000
+        );
+        let (start, end) = (20, 10);
+        assert_eq!(ib.iter_range(start..end).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_indexblock_binary_search() {
+        let mut cells = make_grid_disk(50);
+        cells.sort_unstable();
+        let missing = cells.remove(2000);
+        let ib = IndexBlock::from(cells.as_slice());
+
+        for pos in [0, 1, 1023, 1024, 1025, 4000, cells.len() - 1] {
+            assert_eq!(ib.binary_search(&cells[pos]).unwrap(), Ok(pos));
+        }
+        assert_eq!(
+            ib.binary_search(&missing).unwrap(),
+            cells.binary_search(&missing)
+        );
+
+        let smaller = H3Cell::new(cells[0].h3index() - 1);
+        assert_eq!(ib.binary_search(&smaller).unwrap(), Err(0));
+        let larger = H3Cell::new(cells[cells.len() - 1].h3index() + 1);
+        assert_eq!(ib.binary_search(&larger).unwrap(), Err(cells.len()));
+
+        let empty = IndexBlock::<H3Cell>::from(Vec::new());
+        assert_eq!(empty.binary_search(&cells[0]).unwrap(), Err(0));
+        assert_eq!(empty.iter_uncompressed().unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_indexblock_delta_varint() {
+        let mut cells = make_grid_disk(50);
+        let unsorted = IndexBlock::with_encoding(cells.as_slice(), BlockEncoding::DeltaVarint);
+        assert_eq!(unsorted.encoding().unwrap(), BlockEncoding::DeltaVarint);
+        assert_eq!(
+            unsorted.iter_uncompressed().unwrap().collect::<Vec<_>>(),
+            cells
+        );
+
+        cells.sort_unstable();
+        let byte_columns = IndexBlock::from(cells.as_slice());
+        assert_eq!(byte_columns.encoding().unwrap(), BlockEncoding::ByteColumns);
+        let delta = IndexBlock::with_encoding(cells.as_slice(), BlockEncoding::DeltaVarint);
+        assert!(delta.block_data.len() < byte_columns.block_data.len());
+
+        let mut decompressor = Decompressor::default();
+        assert_eq!(
+            decompressor
+                .decompress_block(&delta)
+                .unwrap()
+                .collect::<Vec<_>>(),
+            cells
+        );
+        assert_eq!(delta.get(3000).unwrap(), Some(cells[3000]));
+        assert_eq!(
+            delta.iter_range(1000..1100).unwrap().collect::<Vec<_>>(),
+            cells[1000..1100].to_vec()
+        );
+        assert_eq!(delta.binary_search(&cells[2500]).unwrap(), Ok(2500));
+
+        // blocks too small to get compressed
+        let small = &cells[..2];
+        let delta_small = IndexBlock::with_encoding(small, BlockEncoding::DeltaVarint);
+        assert!(!delta_small.is_compressed());
+        assert_eq!(
+            delta_small.iter_uncompressed().unwrap().collect::<Vec<_>>(),
+            small.to_vec()
+        );
+    }
+
+    #[test]
+    fn test_varint() {
+        for value in [0_i64, 1, -1, 127, 128, -129, i64::MAX, i64::MIN] {
+            let mut buf = Vec::new();
+            write_varint(&mut buf, zigzag_encode(value));
+            assert!(buf.len() <= super::MAX_VARINT_LEN);
+            let (decoded, len) = read_varint(&buf).unwrap();
+            assert_eq!(len, buf.len());
+            assert_eq!(zigzag_decode(decoded), value);
+        }
+        assert!(read_varint(&[0x80, 0x80]).is_err());
+    }
+
+    #[test]
+    fn test_indexblock_unsupported_encoding() {
+        let mut ib = IndexBlock::from(make_grid_disk(3).as_slice());
+        ib.block_data[4] = 200;
+        assert!(ib.encoding().is_err());
+        assert!(ib.iter_uncompressed().is_err());
+    }
+
+    #[cfg(feature = "use-serde")]
+    #[test]
+    fn serde_delta_varint() {
+        let mut cells = make_grid_disk(20);
+        cells.sort_unstable();
+        let ib = IndexBlock::with_encoding(cells.as_slice(), BlockEncoding::DeltaVarint);
+        let byte_data = bincode::serialize(&ib).unwrap();
+        let ib_de = bincode::deserialize::<IndexBlock<H3Cell>>(&byte_data).unwrap();
+        assert_eq!(ib_de.encoding().unwrap(), BlockEncoding::DeltaVarint);
+        assert_eq!(
+            ib_de.iter_uncompressed().unwrap().collect::<Vec<_>>(),
+            cells
+        );
+    }
+
+    /// the layout of blocks serialized by earlier versions
+    #[cfg(feature = "use-serde")]
+    #[derive(serde::Serialize)]
+    struct LegacyIndexBlock {
+        num_indexes: usize,
+        compressed: bool,
+        block_data: Vec<u8>,
+        phantom_data: std::marker::PhantomData<()>,
+    }
+
+    #[cfg(feature = "use-serde")]
+    #[test]
+    fn serde_legacy_block() {
+        use crate::Index;
+
+        for k in [0, 3] {
+            let cells = make_grid_disk(k);
+            let byte_offset = cells.len();
+            let mut buf = vec![255u8; cells.len() * 8];
+            for (pos, cell) in cells.iter().enumerate() {
+                for (byte_i, byte) in cell.h3index().to_le_bytes().iter().enumerate() {
+                    buf[pos + (byte_i * byte_offset)] = *byte;
+                }
+            }
+            let compressed = cells.len() >= 4;
+            let legacy = LegacyIndexBlock {
+                num_indexes: cells.len(),
+                compressed,
+                block_data: if compressed {
+                    lz4_flex::compress(&buf)
+                } else {
+                    buf
+                },
+                phantom_data: std::marker::PhantomData,
+            };
+
+            let ib: IndexBlock<H3Cell> =
+                bincode::deserialize(&bincode::serialize(&legacy).unwrap()).unwrap();
+            assert_eq!(ib, IndexBlock::from(cells.as_slice()));
+        }
+    }
+
+    #[cfg(feature = "use-serde")]
+    #[test]
+    fn serde_legacy_block_invalid_size() {
+        for (num_indexes, compressed) in [
+            (usize::MAX, false),
+            (usize::MAX, true),
+            (usize::MAX / 8, true),
+            (3, false),
+        ] {
+            let legacy = LegacyIndexBlock {
+                num_indexes,
+                compressed,
+                block_data: vec![0; 16],
+                phantom_data: std::marker::PhantomData,
+            };
+            assert!(bincode::deserialize::<IndexBlock<H3Cell>>(
+                &bincode::serialize(&legacy).unwrap()
+            )
+            .is_err());
+        }
+    }
+
+    #[cfg(feature = "use-serde")]
+    #[test]
+    fn serde_invalid_block() {
+        let ib = IndexBlock::from(make_grid_disk(30).as_slice());
+        let mut byte_data = bincode::serialize(&ib).unwrap();
+        // increase the number of indexes by 1024, so the chunk table does not match anymore
+        byte_data[1] += 4;
+        assert!(bincode::deserialize::<IndexBlock<H3Cell>>(&byte_data).is_err());
+    }
+
+    #[cfg(feature = "use-serde")]
+    #[test]
+    fn serde_invalid_chunk_len() {
+        let ib = IndexBlock::from(make_grid_disk(3).as_slice());
+        let mut byte_data = bincode::serialize(&ib).unwrap();
+        let header_pos = byte_data
+            .windows(super::BLOCK_MAGIC.len())
+            .position(|w| w == super::BLOCK_MAGIC)
+            .unwrap();
+        // the number of chunks still matches, but the chunk length exceeds the supported one
+        byte_data[(header_pos + 5)..(header_pos + 9)].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(bincode::deserialize::<IndexBlock<H3Cell>>(&byte_data).is_err());
+    }
+
     #[cfg(feature = "use-serde")]
     #[test]
     fn serde_roundtrip() {
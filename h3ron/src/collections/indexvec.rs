@@ -1,4 +1,5 @@
-use crate::{Error, FromH3Index, Index};
+use crate::{great_circle_distance_m, Error, FromH3Index, H3Cell, Index, ToCoordinate};
+use geo_types::Coordinate;
 use h3ron_h3_sys::H3Index;
 use std::marker::PhantomData;
 
@@ -134,11 +135,52 @@ impl<T: FromH3Index + Index> IndexVec<T> {
         self.iter_unchecked().count()
     }
 
+    /// the inner vec including the empty `0` values
+    #[cfg(feature = "arrow")]
+    pub(crate) fn into_h3index_vec(self) -> Vec<H3Index> {
+        self.inner_vec
+    }
+
+    /// create from a vec which may contain empty `0` values. No validation is performed.
+    #[cfg(feature = "arrow")]
+    pub(crate) const fn from_h3index_vec(inner_vec: Vec<H3Index>) -> Self {
+        Self {
+            inner_vec,
+            phantom: PhantomData,
+        }
+    }
+
     pub fn push(&mut self, item: T) {
         self.inner_vec.push(item.h3index());
     }
 }
 
+impl IndexVec<H3Cell> {
+    /// The great circle distances in meters from the centroids of the contained cells
+    /// to `coordinate`.
+    ///
+    /// The distances are returned in the order of [`IndexVec::iter`].
+    pub fn distances_m_to_coordinate(
+        &self,
+        coordinate: &Coordinate<f64>,
+    ) -> Result<Vec<f64>, Error> {
+        self.iter()
+            .map(|cell| {
+                cell.to_coordinate()
+                    .map(|centroid| great_circle_distance_m(&centroid, coordinate))
+            })
+            .collect()
+    }
+
+    /// The great circle distances in meters from the centroids of the contained cells
+    /// to the centroid of `cell`.
+    ///
+    /// The distances are returned in the order of [`IndexVec::iter`].
+    pub fn distances_m_to(&self, cell: &H3Cell) -> Result<Vec<f64>, Error> {
+        self.distances_m_to_coordinate(&cell.to_coordinate()?)
+    }
+}
+
 impl<'a, T: FromH3Index + Index> IntoIterator for &'a IndexVec<T> {
     type Item = T;
     type IntoIter = UncheckedIter<'a, T>;
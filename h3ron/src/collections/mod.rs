@@ -17,14 +17,17 @@ pub use hashbrown;
 
 pub use compactedcellvec::CompactedCellVec;
 #[cfg(feature = "lz4_flex")]
-pub use compressed::{Decompressor, IndexBlock};
+pub use compressed::{BlockEncoding, Decompressor, IndexBlock};
+pub use sortedcellset::SortedCellSet;
 #[cfg(feature = "roaring")]
 pub use treemap::H3Treemap;
 
-use crate::{H3Cell, H3DirectedEdge, Index};
+use crate::{H3Cell, H3DirectedEdge, H3Edge, Index};
 
+pub mod cellmap;
 pub mod compactedcellvec;
 pub mod indexvec;
+pub mod sortedcellset;
 
 #[cfg(feature = "lz4_flex")]
 pub mod compressed;
@@ -40,6 +43,8 @@ pub trait ContainsIndex<I: Index> {
 pub type HashMap<K, V> = hashbrown::HashMap<K, V, RandomState>;
 pub type HashSet<V> = hashbrown::HashSet<V, RandomState>;
 pub type H3EdgeMap<V> = HashMap<H3DirectedEdge, V>;
+pub type H3UndirectedEdgeMap<V> = HashMap<H3Edge, V>;
+pub type H3UndirectedEdgeSet = HashSet<H3Edge>;
 pub type H3CellMap<V> = HashMap<H3Cell, V>;
 pub type H3CellSet = HashSet<H3Cell>;
 
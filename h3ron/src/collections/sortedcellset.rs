@@ -0,0 +1,193 @@
+use std::borrow::Borrow;
+
+#[cfg(feature = "use-serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::collections::ContainsIndex;
+use crate::{Error, H3Cell, Index, H3_MAX_RESOLUTION};
+
+/// Set of cells backed by a sorted `Vec`.
+///
+/// Compared to a [`H3CellSet`](crate::collections::H3CellSet) this uses less memory and
+/// allows finding all descendants of a cell using binary searches, but inserting and removing
+/// single cells is more expensive.
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+#[cfg_attr(feature = "use-serde", derive(Serialize, Deserialize))]
+pub struct SortedCellSet {
+    cells: Vec<H3Cell>,
+}
+
+impl SortedCellSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a cell to the set. Returns true if the cell was not already present in the set.
+    pub fn insert(&mut self, cell: H3Cell) -> bool {
+        match self.cells.binary_search(&cell) {
+            Ok(_) => false,
+            Err(pos) => {
+                self.cells.insert(pos, cell);
+                true
+            }
+        }
+    }
+
+    /// Removes a cell from the set. Returns true if the cell was present in the set.
+    pub fn remove(&mut self, cell: &H3Cell) -> bool {
+        match self.cells.binary_search(cell) {
+            Ok(pos) => {
+                self.cells.remove(pos);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    pub fn contains(&self, cell: &H3Cell) -> bool {
+        self.cells.binary_search(cell).is_ok()
+    }
+
+    pub const fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The cells in ascending order
+    pub fn as_slice(&self) -> &[H3Cell] {
+        &self.cells
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = H3Cell> + '_ {
+        self.cells.iter().copied()
+    }
+
+    /// All cells of `self` of resolution `resolution` located within `parent`.
+    ///
+    /// For `resolution` being the resolution of `parent` this will be `parent` itself - in
+    /// case it is contained in `self`.
+    pub fn descendants_at(&self, parent: &H3Cell, resolution: u8) -> Result<&[H3Cell], Error> {
+        let range = parent.children_h3index_range(resolution)?;
+        let start = self
+            .cells
+            .partition_point(|cell| cell.h3index() < *range.start());
+        let end =
+            start + self.cells[start..].partition_point(|cell| cell.h3index() <= *range.end());
+        Ok(&self.cells[start..end])
+    }
+
+    /// Iterate over all cells of `self` located within `parent` - including `parent` itself.
+    pub fn descendants_in(
+        &self,
+        parent: &H3Cell,
+    ) -> Result<impl Iterator<Item = H3Cell> + '_, Error> {
+        let slices = (parent.resolution()..=H3_MAX_RESOLUTION)
+            .map(|resolution| self.descendants_at(parent, resolution))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(slices.into_iter().flatten().copied())
+    }
+}
+
+impl From<Vec<H3Cell>> for SortedCellSet {
+    fn from(mut cells: Vec<H3Cell>) -> Self {
+        cells.sort_unstable();
+        cells.dedup();
+        Self { cells }
+    }
+}
+
+impl From<SortedCellSet> for Vec<H3Cell> {
+    fn from(sorted_cell_set: SortedCellSet) -> Self {
+        sorted_cell_set.cells
+    }
+}
+
+impl<Q> FromIterator<Q> for SortedCellSet
+where
+    Q: Borrow<H3Cell>,
+{
+    fn from_iter<I: IntoIterator<Item = Q>>(iter: I) -> Self {
+        Self::from(
+            iter.into_iter()
+                .map(|cell| *cell.borrow())
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+impl<Q> Extend<Q> for SortedCellSet
+where
+    Q: Borrow<H3Cell>,
+{
+    fn extend<I: IntoIterator<Item = Q>>(&mut self, iter: I) {
+        self.cells
+            .extend(iter.into_iter().map(|cell| *cell.borrow()));
+        self.cells.sort_unstable();
+        self.cells.dedup();
+    }
+}
+
+impl ContainsIndex<H3Cell> for SortedCellSet {
+    fn contains_index(&self, index: &H3Cell) -> bool {
+        self.contains(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collections::H3CellSet;
+    use crate::H3Cell;
+
+    use super::SortedCellSet;
+
+    fn parent() -> H3Cell {
+        H3Cell::try_from(0x85283473fffffff_u64).unwrap()
+    }
+
+    #[test]
+    fn insert_remove_contains() {
+        let mut set: SortedCellSet = parent().grid_disk(1).unwrap().iter().collect();
+        assert_eq!(set.len(), 7);
+        assert!(!set.insert(parent()));
+        assert!(set.remove(&parent()));
+        assert!(!set.contains(&parent()));
+        assert!(set.insert(parent()));
+        assert!(set.as_slice().windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn descendants() {
+        let mut set = SortedCellSet::new();
+        set.extend(parent().grid_disk(1).unwrap().iter());
+        set.extend(parent().get_children(7).unwrap().iter().step_by(2));
+        set.extend(
+            parent()
+                .grid_ring(1)
+                .unwrap()
+                .iter()
+                .flat_map(|neighbor| Vec::from(neighbor.get_children(8).unwrap())),
+        );
+        set.insert(parent().get_parent(3).unwrap());
+
+        assert_eq!(set.descendants_at(&parent(), 7).unwrap().len(), 25);
+        assert!(set.descendants_at(&parent(), 8).unwrap().is_empty());
+        assert_eq!(set.descendants_at(&parent(), 5).unwrap(), &[parent()]);
+
+        let expected: H3CellSet = std::iter::once(parent())
+            .chain(parent().get_children(7).unwrap().iter().step_by(2))
+            .collect();
+        let found: H3CellSet = set.descendants_in(&parent()).unwrap().collect();
+        assert_eq!(found, expected);
+
+        assert!(
+            set.descendants_in(&parent().get_parent(3).unwrap())
+                .unwrap()
+                .count()
+                > 100
+        );
+        assert!(set.descendants_at(&parent(), 4).is_err());
+    }
+}
@@ -1,12 +1,14 @@
 use std::borrow::Borrow;
 use std::error::Error;
+use std::fmt::{Debug, Formatter};
 use std::marker::PhantomData;
 
 use roaring::RoaringTreemap;
 
 use crate::collections::ContainsIndex;
-use crate::Index;
+use crate::{H3Cell, Index, H3_MAX_RESOLUTION};
 
+mod ops;
 #[cfg(feature = "use-serde")]
 pub mod serde;
 
@@ -42,6 +44,23 @@ where
     }
 }
 
+impl<T> PartialEq for H3Treemap<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.treemap == other.treemap
+    }
+}
+
+impl<T> Eq for H3Treemap<T> {}
+
+impl<T> Debug for H3Treemap<T>
+where
+    T: Index + Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
 impl<T> Default for H3Treemap<T>
 where
     T: Index,
@@ -71,6 +90,27 @@ where
         self.treemap.insert(index.h3index())
     }
 
+    /// Removes a value from the set. Returns true if the value was present in the set.
+    #[inline]
+    pub fn remove(&mut self, index: &T) -> bool {
+        self.treemap.remove(index.h3index())
+    }
+
+    /// Retains only the values for which `f` returns true.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let removed: Vec<_> = self
+            .treemap
+            .iter()
+            .filter(|h3index| !f(&T::new(*h3index)))
+            .collect();
+        for h3index in removed {
+            self.treemap.remove(h3index);
+        }
+    }
+
     #[inline]
     pub fn len(&self) -> usize {
         self.treemap.len() as usize
@@ -101,6 +141,30 @@ where
         self.treemap.is_superset(&rhs.treemap)
     }
 
+    /// The union of `self` and `rhs`. Same as `&self | &rhs`.
+    #[inline]
+    pub fn union(&self, rhs: &Self) -> Self {
+        self | rhs
+    }
+
+    /// The values contained in both, `self` and `rhs`. Same as `&self & &rhs`.
+    #[inline]
+    pub fn intersection(&self, rhs: &Self) -> Self {
+        self & rhs
+    }
+
+    /// The values contained in `self`, but not in `rhs`. Same as `&self - &rhs`.
+    #[inline]
+    pub fn difference(&self, rhs: &Self) -> Self {
+        self - rhs
+    }
+
+    /// The values contained in either `self` or `rhs`, but not in both. Same as `&self ^ &rhs`.
+    #[inline]
+    pub fn symmetric_difference(&self, rhs: &Self) -> Self {
+        self ^ rhs
+    }
+
     pub fn iter(&self) -> Iter<T> {
         Iter {
             inner_iter: self.treemap.iter(),
@@ -151,6 +215,38 @@ where
     }
 }
 
+impl H3Treemap<H3Cell> {
+    /// Iterate over all cells of `self` located within `parent` - including `parent` itself.
+    ///
+    /// The cells are found using range scans over the children of `parent` at each
+    /// resolution, not by testing every cell of `self`.
+    pub fn descendants_in(
+        &self,
+        parent: &H3Cell,
+    ) -> Result<impl Iterator<Item = H3Cell> + '_, crate::Error> {
+        let ranges = (parent.resolution()..=H3_MAX_RESOLUTION)
+            .map(|resolution| parent.children_h3index_range(resolution))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(ranges.into_iter().flat_map(move |range| {
+            let mut iter = self.treemap.iter();
+            iter.advance_to(*range.start());
+            iter.take_while(move |h3index| h3index <= range.end())
+                .map(H3Cell::new)
+        }))
+    }
+}
+
+impl<T, Q> Extend<Q> for H3Treemap<T>
+where
+    Q: Borrow<T>,
+    T: Index,
+{
+    fn extend<I: IntoIterator<Item = Q>>(&mut self, iter: I) {
+        self.treemap
+            .extend(iter.into_iter().map(|c| c.borrow().h3index()));
+    }
+}
+
 impl<I: Index> ContainsIndex<I> for H3Treemap<I> {
     fn contains_index(&self, index: &I) -> bool {
         self.contains(index)
@@ -192,4 +288,92 @@ mod tests {
         }
         assert_eq!(treemap.iter().count(), 7);
     }
+
+    fn disk(k: u32) -> H3Treemap<H3Cell> {
+        H3Cell::try_from(0x89283080ddbffff_u64)
+            .unwrap()
+            .grid_disk(k)
+            .unwrap()
+            .iter()
+            .collect()
+    }
+
+    fn ring(k: u32) -> H3Treemap<H3Cell> {
+        H3Cell::try_from(0x89283080ddbffff_u64)
+            .unwrap()
+            .grid_ring(k)
+            .unwrap()
+            .iter()
+            .collect()
+    }
+
+    #[test]
+    fn set_algebra() {
+        assert_eq!(disk(1).union(&ring(2)), disk(2));
+        assert_eq!(disk(2).intersection(&ring(2)), ring(2));
+        assert_eq!(disk(2).difference(&ring(2)), disk(1));
+        assert_eq!(disk(1).symmetric_difference(&disk(2)), ring(2));
+
+        // operators
+        assert_eq!(disk(1) | ring(2), disk(2));
+        assert_eq!(&disk(2) & &ring(2), ring(2));
+        assert_eq!(disk(2) - &ring(2), disk(1));
+        assert_eq!(&disk(1) ^ disk(2), ring(2));
+
+        let mut treemap = disk(1);
+        treemap |= ring(2);
+        assert_eq!(treemap, disk(2));
+        treemap -= &disk(1);
+        assert_eq!(treemap, ring(2));
+        treemap ^= disk(2);
+        assert_eq!(treemap, disk(1));
+        treemap &= &ring(1);
+        assert_eq!(treemap, ring(1));
+    }
+
+    #[test]
+    fn descendants_in() {
+        let parent = H3Cell::try_from(0x85283473fffffff_u64).unwrap();
+        let mut treemap: H3Treemap<H3Cell> = parent.grid_disk(1).unwrap().iter().collect();
+        treemap.extend(parent.get_children(7).unwrap().iter().step_by(3));
+        treemap.extend(
+            parent
+                .grid_ring(1)
+                .unwrap()
+                .first()
+                .unwrap()
+                .get_children(8)
+                .unwrap()
+                .iter(),
+        );
+        treemap.insert(parent.get_parent(4).unwrap());
+
+        let mut expected: Vec<_> = std::iter::once(parent)
+            .chain(parent.get_children(7).unwrap().iter().step_by(3))
+            .collect();
+        expected.sort_unstable();
+        let mut found: Vec<_> = treemap.descendants_in(&parent).unwrap().collect();
+        found.sort_unstable();
+        assert_eq!(found, expected);
+
+        let child = parent.center_child(9).unwrap();
+        assert_eq!(treemap.descendants_in(&child).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn remove_extend_retain() {
+        let center = H3Cell::try_from(0x89283080ddbffff_u64).unwrap();
+        let mut treemap = disk(1);
+        assert!(treemap.remove(&center));
+        assert!(!treemap.remove(&center));
+        assert_eq!(treemap, ring(1));
+
+        treemap.extend(ring(2).iter());
+        treemap.extend(std::iter::once(center));
+        assert_eq!(treemap, disk(2));
+
+        treemap.retain(|cell| cell != &center);
+        assert_eq!(treemap.len(), 18);
+        assert!(!treemap.contains(&center));
+    }
 }
@@ -0,0 +1,75 @@
+//! Set operations as operators. The operations are delegated to the operators of
+//! [`roaring::RoaringTreemap`].
+use std::marker::PhantomData;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Sub, SubAssign};
+
+use crate::collections::H3Treemap;
+
+macro_rules! treemap_op {
+    ($op_trait:ident, $op_fn:ident, $assign_trait:ident, $assign_fn:ident) => {
+        impl<T> $op_trait<H3Treemap<T>> for H3Treemap<T> {
+            type Output = H3Treemap<T>;
+
+            fn $op_fn(self, rhs: H3Treemap<T>) -> Self::Output {
+                H3Treemap {
+                    treemap: $op_trait::$op_fn(self.treemap, rhs.treemap),
+                    phantom_data: PhantomData,
+                }
+            }
+        }
+
+        impl<T> $op_trait<&H3Treemap<T>> for H3Treemap<T> {
+            type Output = H3Treemap<T>;
+
+            fn $op_fn(self, rhs: &H3Treemap<T>) -> Self::Output {
+                H3Treemap {
+                    treemap: $op_trait::$op_fn(self.treemap, &rhs.treemap),
+                    phantom_data: PhantomData,
+                }
+            }
+        }
+
+        impl<T> $op_trait<H3Treemap<T>> for &H3Treemap<T> {
+            type Output = H3Treemap<T>;
+
+            fn $op_fn(self, rhs: H3Treemap<T>) -> Self::Output {
+                H3Treemap {
+                    treemap: $op_trait::$op_fn(&self.treemap, rhs.treemap),
+                    phantom_data: PhantomData,
+                }
+            }
+        }
+
+        impl<T> $op_trait<&H3Treemap<T>> for &H3Treemap<T> {
+            type Output = H3Treemap<T>;
+
+            fn $op_fn(self, rhs: &H3Treemap<T>) -> Self::Output {
+                H3Treemap {
+                    treemap: $op_trait::$op_fn(&self.treemap, &rhs.treemap),
+                    phantom_data: PhantomData,
+                }
+            }
+        }
+
+        impl<T> $assign_trait<H3Treemap<T>> for H3Treemap<T> {
+            fn $assign_fn(&mut self, rhs: H3Treemap<T>) {
+                $assign_trait::$assign_fn(&mut self.treemap, rhs.treemap);
+            }
+        }
+
+        impl<T> $assign_trait<&H3Treemap<T>> for H3Treemap<T> {
+            fn $assign_fn(&mut self, rhs: &H3Treemap<T>) {
+                $assign_trait::$assign_fn(&mut self.treemap, &rhs.treemap);
+            }
+        }
+    };
+}
+
+// union
+treemap_op!(BitOr, bitor, BitOrAssign, bitor_assign);
+// intersection
+treemap_op!(BitAnd, bitand, BitAndAssign, bitand_assign);
+// difference
+treemap_op!(Sub, sub, SubAssign, sub_assign);
+// symmetric difference
+treemap_op!(BitXor, bitxor, BitXorAssign, bitxor_assign);
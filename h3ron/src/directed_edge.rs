@@ -1,4 +1,4 @@
-use std::fmt::{self, Debug, Formatter};
+use std::fmt::{self, Debug, Display, Formatter, LowerHex};
 use std::mem::MaybeUninit;
 use std::ops::Deref;
 use std::os::raw::c_int;
@@ -6,10 +6,13 @@ use std::str::FromStr;
 
 use geo::{LineString, MultiLineString};
 #[cfg(feature = "use-serde")]
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use h3ron_h3_sys::H3Index;
 
+use crate::bits::{H3_CELL_MODE, H3_DIRECTED_EDGE_MODE};
+#[cfg(feature = "use-serde")]
+use crate::index::{deserialize_index, serialize_index};
 use crate::index::{index_from_str, Index};
 use crate::iter::CellBoundaryIter;
 use crate::to_geo::{ToLineString, ToMultiLineString};
@@ -17,13 +20,12 @@ use crate::{Error, FromH3Index, H3Cell, ToCoordinate};
 
 /// H3 Index representing an directed H3 edge
 #[derive(PartialOrd, PartialEq, Clone, Hash, Eq, Ord, Copy)]
-#[cfg_attr(feature = "use-serde", derive(Serialize, Deserialize))]
 #[repr(transparent)]
 pub struct H3DirectedEdge(H3Index);
 
 impl Debug for H3DirectedEdge {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "H3DirectedEdge({})", self.to_string())
+        write!(f, "H3DirectedEdge({})", self)
     }
 }
 
@@ -104,11 +106,14 @@ impl H3DirectedEdge {
     /// # Returns
     /// If the built index is invalid, returns an Error.
     pub fn origin_cell(&self) -> Result<H3Cell, Error> {
-        let mut cell_h3index: H3Index = 0;
-        Error::check_returncode(unsafe {
-            h3ron_h3_sys::getDirectedEdgeOrigin(self.h3index(), &mut cell_h3index)
-        })
-        .map(|_| H3Cell::new(cell_h3index))
+        let bits = self.bits();
+        if bits.mode() == H3_DIRECTED_EDGE_MODE {
+            Ok(H3Cell::new(
+                bits.with_mode(H3_CELL_MODE).with_reserved(0).h3index(),
+            ))
+        } else {
+            Err(Error::DirectedEdgeInvalid)
+        }
     }
 
     /// Retrieves a `H3EdgeCells` of the origin and destination cell of the
@@ -206,9 +211,15 @@ impl Index for H3DirectedEdge {
     }
 }
 
-impl ToString for H3DirectedEdge {
-    fn to_string(&self) -> String {
-        format!("{:x}", self.0)
+impl Display for H3DirectedEdge {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:x}", self.0)
+    }
+}
+
+impl LowerHex for H3DirectedEdge {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        LowerHex::fmt(&self.0, f)
     }
 }
 
@@ -220,6 +231,27 @@ impl FromStr for H3DirectedEdge {
     }
 }
 
+/// Serialized as hex string for human-readable formats like JSON, as `u64` otherwise.
+#[cfg(feature = "use-serde")]
+impl Serialize for H3DirectedEdge {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_index(self, "H3DirectedEdge", serializer)
+    }
+}
+
+#[cfg(feature = "use-serde")]
+impl<'de> Deserialize<'de> for H3DirectedEdge {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_index("H3DirectedEdge", deserializer)
+    }
+}
+
 impl ToLineString for H3DirectedEdge {
     type Error = Error;
 
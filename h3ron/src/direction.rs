@@ -1,7 +1,4 @@
-use crate::{Error, Index, H3_MAX_RESOLUTION};
-
-const H3_PER_DIGIT_OFFSET: u8 = 3;
-const H3_DIGIT_MASK: u8 = 7;
+use crate::{Error, H3IndexBits, Index};
 
 /// H3 digit representing ijk+ axes direction.
 /// See [documentation](https://h3geo.org/docs/core-library/h3Indexing/#introduction)
@@ -93,44 +90,31 @@ impl H3Direction {
         if target_resolution > index.resolution() {
             return Err(Error::ResMismatch);
         }
-        direction(index.h3index() as u64, offset(target_resolution))
+        index.bits().direction(target_resolution)
     }
 
     /// iterate over all directions leading to the given `index` starting from
     /// resolution 0 to the resolution of the `index`.
     pub fn iter_directions_over_resolutions<I: Index>(index: &I) -> ResolutionDirectionIter {
         ResolutionDirectionIter {
-            h3index: index.h3index(),
-            stop_offset: offset(index.resolution()),
-            current_offset: offset(1),
+            bits: index.bits(),
+            current_resolution: 1,
         }
     }
 }
 
-#[inline]
-fn offset(target_resolution: u8) -> u64 {
-    u64::from(H3_MAX_RESOLUTION.saturating_sub(target_resolution) * H3_PER_DIGIT_OFFSET)
-}
-
-#[inline]
-fn direction(h3index: u64, offset: u64) -> Result<H3Direction, Error> {
-    let dir = (h3index >> offset) & u64::from(H3_DIGIT_MASK);
-    H3Direction::try_from(dir as u8)
-}
-
 pub struct ResolutionDirectionIter {
-    h3index: u64,
-    stop_offset: u64,
-    current_offset: u64,
+    bits: H3IndexBits,
+    current_resolution: u8,
 }
 
 impl Iterator for ResolutionDirectionIter {
     type Item = Result<H3Direction, Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current_offset >= self.stop_offset {
-            let dir_result = direction(self.h3index, self.current_offset);
-            self.current_offset -= u64::from(H3_PER_DIGIT_OFFSET);
+        if self.current_resolution <= self.bits.resolution() {
+            let dir_result = self.bits.direction(self.current_resolution);
+            self.current_resolution += 1;
             Some(dir_result)
         } else {
             None
@@ -0,0 +1,314 @@
+use std::fmt::{self, Debug, Display, Formatter, LowerHex};
+use std::ops::Deref;
+use std::str::FromStr;
+
+use geo::{LineString, MultiLineString};
+#[cfg(feature = "use-serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use h3ron_h3_sys::H3Index;
+
+use crate::bits::{H3_DIRECTED_EDGE_MODE, H3_EDGE_MODE};
+#[cfg(feature = "use-serde")]
+use crate::index::{deserialize_index, serialize_index};
+use crate::index::{index_from_str, Index};
+use crate::to_geo::{ToLineString, ToMultiLineString};
+use crate::{Error, FromH3Index, H3Cell, H3DirectedEdge, H3IndexBits};
+
+#[inline(always)]
+const fn with_mode(h3index: H3Index, mode: u8) -> H3Index {
+    H3IndexBits::new(h3index).with_mode(mode).h3index()
+}
+
+/// H3 Index representing an undirected H3 edge - the boundary between two neighboring cells.
+///
+/// Both [`H3DirectedEdge`] values connecting two cells map to the same `H3Edge`. The edge is
+/// stored using the H3 edge mode (3) and the direction of the "canonical" directed edge - which is
+/// the directed edge leading from the cell with the smaller h3index to the cell with the
+/// larger one.
+#[derive(PartialOrd, PartialEq, Clone, Hash, Eq, Ord, Copy)]
+#[repr(transparent)]
+pub struct H3Edge(H3Index);
+
+impl Debug for H3Edge {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "H3Edge({})", self)
+    }
+}
+
+/// convert to index including validation
+impl TryFrom<u64> for H3Edge {
+    type Error = Error;
+
+    fn try_from(h3index: H3Index) -> Result<Self, Self::Error> {
+        let index = Self::new(h3index);
+        index.validate()?;
+        Ok(index)
+    }
+}
+
+impl TryFrom<H3DirectedEdge> for H3Edge {
+    type Error = Error;
+
+    fn try_from(directed_edge: H3DirectedEdge) -> Result<Self, Self::Error> {
+        Self::from_directed_edge(directed_edge)
+    }
+}
+
+impl H3Edge {
+    /// Gets the undirected edge between `cell_a` and `cell_b`. The order of the cells
+    /// is not relevant.
+    pub fn from_cells(cell_a: H3Cell, cell_b: H3Cell) -> Result<Self, Error> {
+        let directed_edge = if cell_a < cell_b {
+            cell_a.directed_edge_to(cell_b)?
+        } else {
+            cell_b.directed_edge_to(cell_a)?
+        };
+        Ok(Self::new(with_mode(directed_edge.h3index(), H3_EDGE_MODE)))
+    }
+
+    /// Gets the undirected edge of the given `directed_edge`.
+    pub fn from_directed_edge(directed_edge: H3DirectedEdge) -> Result<Self, Error> {
+        let edge_cells = directed_edge.cells()?;
+        let canonical_edge = if edge_cells.origin < edge_cells.destination {
+            directed_edge
+        } else {
+            edge_cells.destination.directed_edge_to(edge_cells.origin)?
+        };
+        Ok(Self::new(with_mode(canonical_edge.h3index(), H3_EDGE_MODE)))
+    }
+
+    /// The directed edge leading from the cell with the smaller h3index to the
+    /// cell with the larger h3index.
+    ///
+    /// This conversion does not require any calls to libh3.
+    pub fn to_directed_edge(&self) -> H3DirectedEdge {
+        H3DirectedEdge::new(with_mode(self.0, H3_DIRECTED_EDGE_MODE))
+    }
+
+    /// Both directed edges represented by this undirected edge. The first element is the
+    /// same edge as returned by [`H3Edge::to_directed_edge`], the second one
+    /// the reversed edge.
+    pub fn directed_edges(&self) -> Result<[H3DirectedEdge; 2], Error> {
+        let directed_edge = self.to_directed_edge();
+        Ok([directed_edge, directed_edge.reversed()?])
+    }
+
+    /// The two cells separated by this edge, ordered by their h3index.
+    pub fn cells(&self) -> Result<[H3Cell; 2], Error> {
+        let edge_cells = self.to_directed_edge().cells()?;
+        Ok([edge_cells.origin, edge_cells.destination])
+    }
+
+    /// Retrieves the [`LineString`] which forms the boundary between
+    /// the two cells.
+    pub fn boundary_linestring(&self) -> Result<LineString<f64>, Error> {
+        self.to_directed_edge().boundary_linestring()
+    }
+
+    /// Retrieves the exact length of `self` in meters
+    pub fn exact_length_m(&self) -> Result<f64, Error> {
+        self.to_directed_edge().exact_length_m()
+    }
+
+    /// Retrieves the exact length of `self` in kilometers
+    pub fn exact_length_km(&self) -> Result<f64, Error> {
+        self.to_directed_edge().exact_length_km()
+    }
+
+    /// Retrieves the exact length of `self` in radians
+    pub fn exact_length_rads(&self) -> Result<f64, Error> {
+        self.to_directed_edge().exact_length_rads()
+    }
+}
+
+impl FromH3Index for H3Edge {
+    fn from_h3index(h3index: H3Index) -> Self {
+        Self::new(h3index)
+    }
+}
+
+impl Index for H3Edge {
+    fn h3index(&self) -> H3Index {
+        self.0
+    }
+
+    fn new(h3index: H3Index) -> Self {
+        Self(h3index)
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        if self.bits().mode() != H3_EDGE_MODE {
+            return Err(Error::UndirectedEdgeInvalid);
+        }
+        let directed_edge = self.to_directed_edge();
+        if directed_edge.validate().is_err() {
+            return Err(Error::UndirectedEdgeInvalid);
+        }
+        let edge_cells = directed_edge.cells()?;
+        if edge_cells.origin < edge_cells.destination {
+            Ok(())
+        } else {
+            // not the canonical direction
+            Err(Error::UndirectedEdgeInvalid)
+        }
+    }
+}
+
+impl Display for H3Edge {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:x}", self.0)
+    }
+}
+
+impl LowerHex for H3Edge {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        LowerHex::fmt(&self.0, f)
+    }
+}
+
+impl FromStr for H3Edge {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        index_from_str(s)
+    }
+}
+
+/// Serialized as hex string for human-readable formats like JSON, as `u64` otherwise.
+#[cfg(feature = "use-serde")]
+impl Serialize for H3Edge {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_index(self, "H3Edge", serializer)
+    }
+}
+
+#[cfg(feature = "use-serde")]
+impl<'de> Deserialize<'de> for H3Edge {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_index("H3Edge", deserializer)
+    }
+}
+
+impl ToLineString for H3Edge {
+    type Error = Error;
+
+    /// Create a linestring connecting the centroids of the two cells.
+    fn to_linestring(&self) -> Result<LineString<f64>, Self::Error> {
+        self.to_directed_edge().to_linestring()
+    }
+}
+
+/// converts `&[H3Edge]` slices to a `MultiLineString` of the boundaries of the edges.
+impl ToMultiLineString for &[H3Edge] {
+    type Error = Error;
+
+    fn to_multilinestring(&self) -> Result<MultiLineString<f64>, Self::Error> {
+        Ok(MultiLineString(
+            self.iter()
+                .map(H3Edge::boundary_linestring)
+                .collect::<Result<Vec<_>, _>>()?,
+        ))
+    }
+}
+
+impl Deref for H3Edge {
+    type Target = H3Index;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::collections::H3UndirectedEdgeSet;
+
+    use super::*;
+
+    #[test]
+    fn both_directions_map_to_same_edge() {
+        let directed_edge = H3DirectedEdge::new(0x149283080ddbffff);
+        let edge = H3Edge::from_directed_edge(directed_edge).unwrap();
+        let edge_rev = H3Edge::from_directed_edge(directed_edge.reversed().unwrap()).unwrap();
+        assert_eq!(edge, edge_rev);
+        assert!(edge.is_valid());
+
+        let cells = directed_edge.cells().unwrap();
+        assert_eq!(
+            H3Edge::from_cells(cells.origin, cells.destination).unwrap(),
+            edge
+        );
+        assert_eq!(
+            H3Edge::from_cells(cells.destination, cells.origin).unwrap(),
+            edge
+        );
+    }
+
+    #[test]
+    fn directed_edges_roundtrip() {
+        let directed_edge = H3DirectedEdge::new(0x149283080ddbffff);
+        let edge = H3Edge::try_from(directed_edge).unwrap();
+        let directed_edges = edge.directed_edges().unwrap();
+        assert!(directed_edges.contains(&directed_edge));
+        assert!(directed_edges.contains(&directed_edge.reversed().unwrap()));
+
+        let cells = edge.cells().unwrap();
+        assert!(cells[0] < cells[1]);
+        assert_eq!(directed_edges[0].origin_cell().unwrap(), cells[0]);
+    }
+
+    #[test]
+    fn dedup_in_set() {
+        let cell = H3Cell::try_from(0x89283080ddbffff_u64).unwrap();
+        let edges: H3UndirectedEdgeSet = cell
+            .grid_disk(1)
+            .unwrap()
+            .iter()
+            .flat_map(|cell| cell.directed_edges().unwrap().iter().collect::<Vec<_>>())
+            .map(|directed_edge| H3Edge::from_directed_edge(directed_edge).unwrap())
+            .collect();
+        // 6 inner edges, 6 edges between the neighbors and 3 outer edges per neighbor
+        assert_eq!(edges.len(), 30);
+    }
+
+    #[test]
+    fn invalid_edges() {
+        let directed_edge = H3DirectedEdge::new(0x149283080ddbffff);
+        // directed edges are no valid undirected edges
+        assert!(H3Edge::try_from(directed_edge.h3index()).is_err());
+        // cells neither
+        assert!(H3Edge::try_from(0x89283080ddbffff_u64).is_err());
+
+        // the non-canonical direction is invalid
+        let edge = H3Edge::from_directed_edge(directed_edge).unwrap();
+        let non_canonical = with_mode(edge.directed_edges().unwrap()[1].h3index(), H3_EDGE_MODE);
+        assert!(H3Edge::try_from(non_canonical).is_err());
+    }
+
+    #[test]
+    fn length_and_boundary() {
+        let directed_edge = H3DirectedEdge::new(0x149283080ddbffff);
+        let edge = H3Edge::from_directed_edge(directed_edge).unwrap();
+        assert!(
+            (edge.exact_length_m().unwrap() - directed_edge.exact_length_m().unwrap()).abs() < 1e-6
+        );
+        assert_eq!(edge.boundary_linestring().unwrap().0.len(), 2);
+    }
+
+    #[test]
+    fn string_roundtrip() {
+        let edge = H3Edge::from_directed_edge(H3DirectedEdge::new(0x149283080ddbffff)).unwrap();
+        let edge_2 = H3Edge::from_str(&edge.to_string()).unwrap();
+        assert_eq!(edge, edge_2);
+        assert_eq!(format!("{:?}", edge), format!("H3Edge({})", edge));
+    }
+}
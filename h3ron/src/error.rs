@@ -84,6 +84,9 @@ pub enum Error {
 
     #[error("decompression error")]
     DecompressionError(String),
+
+    #[error("arrow error: {0}")]
+    ArrowError(String),
 }
 
 impl Error {
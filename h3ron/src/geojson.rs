@@ -0,0 +1,246 @@
+//! Export of cells, edges and polygons as [GeoJSON](https://geojson.org/) `FeatureCollection`s.
+//!
+//! The features are streamed to the underlying writer, so there is no need to build
+//! the complete `FeatureCollection` in memory.
+use std::hash::BuildHasher;
+use std::io::Write;
+
+use ::geojson::{Feature, Geometry, JsonObject, JsonValue, Value};
+use geo_types::Polygon;
+use serde::Serialize;
+
+use crate::collections::hashbrown::HashMap;
+use crate::collections::indexvec::IndexVec;
+use crate::{Error, H3Cell, H3DirectedEdge, Index, ToPolygon};
+
+/// hide serde_json errors in the io error to avoid having serde_json in the public api.
+impl From<serde_json::Error> for Error {
+    fn from(json_err: serde_json::Error) -> Self {
+        Self::IOError(std::io::Error::from(json_err))
+    }
+}
+
+/// Writes features as GeoJSON `FeatureCollection` to a [`Write`].
+///
+/// The `FeatureCollection` is only complete after calling [`FeatureCollectionWriter::finish`].
+pub struct FeatureCollectionWriter<W: Write> {
+    writer: W,
+    num_features: usize,
+}
+
+impl<W: Write> FeatureCollectionWriter<W> {
+    /// Create a new writer. This writes the header of the `FeatureCollection`.
+    pub fn new(mut writer: W) -> Result<Self, Error> {
+        writer.write_all(br#"{"type":"FeatureCollection","features":["#)?;
+        Ok(Self {
+            writer,
+            num_features: 0,
+        })
+    }
+
+    /// Write a single feature
+    pub fn write_feature(&mut self, feature: &Feature) -> Result<(), Error> {
+        if self.num_features > 0 {
+            self.writer.write_all(b",")?;
+        }
+        serde_json::to_writer(&mut self.writer, feature)?;
+        self.num_features += 1;
+        Ok(())
+    }
+
+    /// Write the polygon of a cell. The properties contain the hex string of the `h3index`.
+    pub fn write_cell(&mut self, cell: &H3Cell) -> Result<(), Error> {
+        let feature = build_feature(Value::from(&cell.to_polygon()?), index_properties(cell));
+        self.write_feature(&feature)
+    }
+
+    /// Write the polygon of a cell together with a `value` property.
+    pub fn write_cell_with_value<V: Serialize>(
+        &mut self,
+        cell: &H3Cell,
+        value: &V,
+    ) -> Result<(), Error> {
+        let mut properties = index_properties(cell);
+        properties.insert("value".to_string(), serde_json::to_value(value)?);
+        let feature = build_feature(Value::from(&cell.to_polygon()?), properties);
+        self.write_feature(&feature)
+    }
+
+    /// Write the boundary linestring of a directed edge. The properties contain the hex string
+    /// of the `h3index`.
+    pub fn write_directed_edge(&mut self, edge: &H3DirectedEdge) -> Result<(), Error> {
+        let feature = build_feature(
+            Value::from(&edge.boundary_linestring()?),
+            index_properties(edge),
+        );
+        self.write_feature(&feature)
+    }
+
+    /// Write a polygon - for example one created using [`ToLinkedPolygons`](crate::ToLinkedPolygons) -
+    /// without any properties.
+    pub fn write_polygon(&mut self, polygon: &Polygon<f64>) -> Result<(), Error> {
+        let feature = build_feature(Value::from(polygon), JsonObject::new());
+        self.write_feature(&feature)
+    }
+
+    /// The number of features written so far.
+    pub const fn num_features(&self) -> usize {
+        self.num_features
+    }
+
+    /// Completes the `FeatureCollection` and returns the inner writer.
+    pub fn finish(mut self) -> Result<W, Error> {
+        self.writer.write_all(b"]}")?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+fn index_properties<I: Index>(index: &I) -> JsonObject {
+    let mut properties = JsonObject::new();
+    properties.insert(
+        "h3index".to_string(),
+        JsonValue::String(format!("{:x}", index.h3index())),
+    );
+    properties
+}
+
+fn build_feature(value: Value, properties: JsonObject) -> Feature {
+    Feature {
+        bbox: None,
+        geometry: Some(Geometry::new(value)),
+        id: None,
+        properties: Some(properties),
+        foreign_members: None,
+    }
+}
+
+/// Conversion to a GeoJSON `FeatureCollection`.
+pub trait ToGeoJson {
+    /// Write `self` as GeoJSON `FeatureCollection` to the given `writer`.
+    fn write_geojson<W: Write>(&self, writer: W) -> Result<(), Error>;
+}
+
+impl ToGeoJson for IndexVec<H3Cell> {
+    fn write_geojson<W: Write>(&self, writer: W) -> Result<(), Error> {
+        let mut fc_writer = FeatureCollectionWriter::new(writer)?;
+        for cell in self.iter() {
+            fc_writer.write_cell(&cell)?;
+        }
+        fc_writer.finish()?;
+        Ok(())
+    }
+}
+
+/// The values are written to the `value` property of the features.
+impl<V, S> ToGeoJson for HashMap<H3Cell, V, S>
+where
+    V: Serialize,
+    S: BuildHasher,
+{
+    fn write_geojson<W: Write>(&self, writer: W) -> Result<(), Error> {
+        let mut fc_writer = FeatureCollectionWriter::new(writer)?;
+        for (cell, value) in self.iter() {
+            fc_writer.write_cell_with_value(cell, value)?;
+        }
+        fc_writer.finish()?;
+        Ok(())
+    }
+}
+
+impl ToGeoJson for [H3DirectedEdge] {
+    fn write_geojson<W: Write>(&self, writer: W) -> Result<(), Error> {
+        let mut fc_writer = FeatureCollectionWriter::new(writer)?;
+        for edge in self.iter() {
+            fc_writer.write_directed_edge(edge)?;
+        }
+        fc_writer.finish()?;
+        Ok(())
+    }
+}
+
+impl ToGeoJson for [Polygon<f64>] {
+    fn write_geojson<W: Write>(&self, writer: W) -> Result<(), Error> {
+        let mut fc_writer = FeatureCollectionWriter::new(writer)?;
+        for polygon in self.iter() {
+            fc_writer.write_polygon(polygon)?;
+        }
+        fc_writer.finish()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use ::geojson::{FeatureCollection, GeoJson};
+
+    use crate::collections::H3CellMap;
+    use crate::{H3Cell, ToLinkedPolygons};
+
+    use super::*;
+
+    fn parse(data: &[u8]) -> FeatureCollection {
+        let geojson: GeoJson = std::str::from_utf8(data).unwrap().parse().unwrap();
+        FeatureCollection::try_from(geojson).unwrap()
+    }
+
+    fn cells() -> IndexVec<H3Cell> {
+        H3Cell::try_from(0x89283080ddbffff_u64)
+            .unwrap()
+            .grid_disk(1)
+            .unwrap()
+    }
+
+    #[test]
+    fn write_cells() {
+        let mut data = Vec::new();
+        cells().write_geojson(&mut data).unwrap();
+        let fc = parse(&data);
+        assert_eq!(fc.features.len(), 7);
+        assert!(fc.features.iter().all(|feature| feature
+            .property("h3index")
+            .and_then(JsonValue::as_str)
+            .map(|s| s.parse::<H3Cell>().is_ok())
+            .unwrap_or_default()));
+    }
+
+    #[test]
+    fn write_cell_map() {
+        let map: H3CellMap<u32> = cells().iter().map(|cell| (cell, 5)).collect();
+        let mut data = Vec::new();
+        map.write_geojson(&mut data).unwrap();
+        let fc = parse(&data);
+        assert_eq!(fc.features.len(), 7);
+        assert!(fc
+            .features
+            .iter()
+            .all(|feature| feature.property("value").and_then(JsonValue::as_u64) == Some(5)));
+    }
+
+    #[test]
+    fn write_edges_and_polygons() {
+        let edges: Vec<_> = H3Cell::try_from(0x89283080ddbffff_u64)
+            .unwrap()
+            .directed_edges()
+            .unwrap()
+            .iter()
+            .collect();
+        let mut data = Vec::new();
+        edges.write_geojson(&mut data).unwrap();
+        assert_eq!(parse(&data).features.len(), 6);
+
+        let polygons = cells().to_linked_polygons(false).unwrap();
+        let mut data = Vec::new();
+        polygons.write_geojson(&mut data).unwrap();
+        assert_eq!(parse(&data).features.len(), 1);
+    }
+
+    #[test]
+    fn write_empty() {
+        let mut data = Vec::new();
+        IndexVec::<H3Cell>::new().write_geojson(&mut data).unwrap();
+        assert!(parse(&data).features.is_empty());
+    }
+}
@@ -1,6 +1,15 @@
-use crate::{Error, FromH3Index, H3Direction};
+use crate::{Error, FromH3Index, H3Direction, H3IndexBits};
 use h3ron_h3_sys::H3Index;
 use std::ffi::CString;
+#[cfg(feature = "use-serde")]
+use std::fmt::{self, Display};
+#[cfg(feature = "use-serde")]
+use std::marker::PhantomData;
+
+#[cfg(feature = "use-serde")]
+use serde::de::{Error as DeError, Unexpected, Visitor};
+#[cfg(feature = "use-serde")]
+use serde::{Deserialize, Deserializer, Serializer};
 
 /// Trait to handle types having a H3 Index like cells and edges
 pub trait Index: Sized + PartialEq + FromH3Index {
@@ -17,7 +26,12 @@ pub trait Index: Sized + PartialEq + FromH3Index {
 
     /// Gets the index resolution (0-15)
     fn resolution(&self) -> u8 {
-        (unsafe { h3ron_h3_sys::getResolution(self.h3index()) }) as u8
+        self.bits().resolution()
+    }
+
+    /// Access to the individual fields of the index
+    fn bits(&self) -> H3IndexBits {
+        H3IndexBits::new(self.h3index())
     }
 
     /// Checks the validity of the index
@@ -65,8 +79,12 @@ where
     }
 }
 
-/// parse an index from its string representation
+/// parse an index from its string representation. An optional `0x` prefix is accepted.
 pub(crate) fn index_from_str<IX: Index>(s: &str) -> Result<IX, Error> {
+    let s = s
+        .strip_prefix("0x")
+        .or_else(|| s.strip_prefix("0X"))
+        .unwrap_or(s);
     let cs = CString::new(s).map_err(|_| Error::Failed)?;
 
     let mut h3index: H3Index = 0;
@@ -76,3 +94,102 @@ pub(crate) fn index_from_str<IX: Index>(s: &str) -> Result<IX, Error> {
     index.validate()?;
     Ok(index)
 }
+
+/// Serializes the index as hex string for human-readable formats and as `u64` for
+/// binary formats.
+#[cfg(feature = "use-serde")]
+pub(crate) fn serialize_index<IX, S>(
+    index: &IX,
+    name: &'static str,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    IX: Index + Display,
+    S: Serializer,
+{
+    if serializer.is_human_readable() {
+        serializer.collect_str(index)
+    } else {
+        serializer.serialize_newtype_struct(name, &index.h3index())
+    }
+}
+
+/// Deserializes indexes serialized by [`serialize_index`].
+///
+/// Human-readable formats additionally accept plain numbers and decimal strings as written by
+/// earlier versions. Indexes of human-readable formats get validated - regardless if given as
+/// string or as number.
+#[cfg(feature = "use-serde")]
+pub(crate) fn deserialize_index<'de, IX, D>(
+    name: &'static str,
+    deserializer: D,
+) -> Result<IX, D::Error>
+where
+    IX: Index,
+    D: Deserializer<'de>,
+{
+    let visitor = IndexVisitor {
+        name,
+        phantom_data: PhantomData,
+    };
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_any(visitor)
+    } else {
+        deserializer.deserialize_newtype_struct(name, visitor)
+    }
+}
+
+#[cfg(feature = "use-serde")]
+struct IndexVisitor<IX> {
+    name: &'static str,
+    phantom_data: PhantomData<IX>,
+}
+
+#[cfg(feature = "use-serde")]
+impl<'de, IX: Index> Visitor<'de> for IndexVisitor<IX> {
+    type Value = IX;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{} as hex string or u64", self.name)
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: DeError,
+    {
+        let index = IX::new(v);
+        index.validate().map_err(E::custom)?;
+        Ok(index)
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: DeError,
+    {
+        let h3index =
+            u64::try_from(v).map_err(|_| E::invalid_value(Unexpected::Signed(v), &self))?;
+        self.visit_u64(h3index)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: DeError,
+    {
+        // earlier versions wrote indexes within strings - like the keys of JSON maps - as
+        // decimal numbers. These have more digits than a hex-encoded u64 can have.
+        if v.len() > 16 && v.bytes().all(|b| b.is_ascii_digit()) {
+            let h3index = v
+                .parse::<u64>()
+                .map_err(|_| E::invalid_value(Unexpected::Str(v), &self))?;
+            return self.visit_u64(h3index);
+        }
+        index_from_str(v).map_err(E::custom)
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        H3Index::deserialize(deserializer).map(IX::new)
+    }
+}
@@ -44,6 +44,15 @@ impl GridDiskBuilder {
         })
     }
 
+    /// Creates a builder in "ring mode", which only iterates over the cells with a
+    /// distance of exactly `k` to the center. Also see [`H3Cell::grid_ring`].
+    ///
+    /// In contrast to [`H3Cell::grid_ring_unsafe`] this does not fail when encountering
+    /// pentagonal distortion.
+    pub fn create_ring(k: u32) -> Result<Self, Error> {
+        Self::create(k, k)
+    }
+
     #[inline(always)]
     fn rewind_iterator(&mut self) {
         self.current_pos = 0;
@@ -101,3 +110,28 @@ impl Iterator for GridDiskBuilder {
         (self.k_ring_size - self.current_pos, None)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::iter::GridDiskBuilder;
+    use crate::H3Cell;
+
+    #[test]
+    fn ring_mode_around_pentagon() {
+        let pentagon = H3Cell::try_from(0x821c07fffffffff_u64).unwrap();
+        let mut builder = GridDiskBuilder::create_ring(2).unwrap();
+        let mut cells: Vec<_> = builder
+            .build_grid_disk(&pentagon)
+            .unwrap()
+            .map(|(cell, k)| {
+                assert_eq!(k, 2);
+                cell
+            })
+            .collect();
+        cells.sort_unstable();
+        let mut expected: Vec<_> = pentagon.grid_ring(2).unwrap().into();
+        expected.sort_unstable();
+        assert_eq!(cells, expected);
+        assert_eq!(cells.len(), 10);
+    }
+}
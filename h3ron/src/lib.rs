@@ -6,6 +6,9 @@
 //! * **use-serde**: serde support.
 //! * **compression**: enables the `collections::compressed` module.
 //! * **roaring**: Enables `collections::H3Treemap` based on the `roaring` crate.
+//! * **use-rayon**: Parallelizes some operations - like the ones of the `morphology` module - using `rayon`.
+//! * **arrow**: Conversions of `IndexVec`, `H3Treemap` and `H3CellMap` to and from Apache Arrow arrays.
+//! * **geojson**: Export of cells, edges and polygons as GeoJSON `FeatureCollection`s.
 //! * **io**: Convenience serialization helpers of the `h3ron::io` module. These are not really related to h3, but helpful for utilities
 //! during development.
 #![warn(clippy::nursery, nonstandard_style)]
@@ -13,37 +16,55 @@
 use std::iter::Iterator;
 use std::os::raw::c_int;
 
-use geo_types::{LineString, Polygon};
+use geo_types::{Coordinate, LineString, Polygon};
 
 use h3ron_h3_sys::{GeoLoop, GeoPolygon, H3Index, LatLng};
 pub use to_geo::{
-    to_linked_polygons, ToAlignedLinkedPolygons, ToCoordinate, ToLinkedPolygons, ToPolygon,
+    to_linked_polygons, to_linked_polygons_with_antimeridian, ToAlignedLinkedPolygons,
+    ToAntimeridianPolygon, ToCoordinate, ToLinkedPolygons, ToPolygon,
 };
 pub use {
-    cell::H3Cell, directed_edge::H3DirectedEdge, direction::H3Direction, error::Error,
-    index::HasH3Resolution, index::Index, localij::CoordIj, to_h3::ToH3Cells,
+    antimeridian::AntimeridianHandling, bits::H3IndexBits, cell::H3Cell,
+    directed_edge::H3DirectedEdge, direction::H3Direction, edge::H3Edge, error::Error,
+    index::HasH3Resolution, index::Index, localij::CoordIj,
+    to_h3::polygon_to_cells_with_containment, to_h3::ContainmentMode, to_h3::ToH3Cells,
+    vertex::H3Vertex,
 };
 
 use crate::collections::indexvec::IndexVec;
 
 #[macro_use]
 pub mod algorithm;
+pub mod antimeridian;
+#[cfg(feature = "arrow")]
+pub mod arrow;
+pub mod binning;
+pub mod bits;
 mod cell;
 pub mod collections;
 mod directed_edge;
 mod direction;
+mod edge;
 pub mod error;
+#[cfg(feature = "geojson")]
+pub mod geojson;
 mod index;
 #[cfg(feature = "io")]
 pub mod io;
 pub mod iter;
 pub mod localij;
+pub mod morphology;
 pub mod to_geo;
 pub mod to_h3;
+pub mod topology;
+mod vertex;
 
 pub const H3_MIN_RESOLUTION: u8 = 0_u8;
 pub const H3_MAX_RESOLUTION: u8 = 15_u8;
 
+/// Mean earth radius in kilometers as used by libh3
+pub const EARTH_RADIUS_KM: f64 = 6_371.007_180_918_475;
+
 /// trait for types which can be created from an `H3Index`
 pub trait FromH3Index {
     fn from_h3index(h3index: H3Index) -> Self;
@@ -209,12 +230,74 @@ pub fn res0_cells() -> IndexVec<H3Cell> {
     index_vec
 }
 
+/// `pentagon_count` returns the number of pentagons per resolution
+pub fn pentagon_count() -> u8 {
+    unsafe { h3ron_h3_sys::pentagonCount() as u8 }
+}
+
+/// provides all pentagons at the given resolution
+pub fn pentagons(h3_resolution: u8) -> Result<IndexVec<H3Cell>, Error> {
+    let mut index_vec = IndexVec::with_length(pentagon_count() as usize);
+    Error::check_returncode(unsafe {
+        h3ron_h3_sys::getPentagons(c_int::from(h3_resolution), index_vec.as_mut_ptr())
+    })
+    .map(|_| index_vec)
+}
+
+/// The great circle distance between two coordinates in radians using the haversine formula.
+///
+/// The coordinates are expected to be in degrees with `x` being the longitude and `y` the latitude.
+pub fn great_circle_distance_rads(c1: &Coordinate<f64>, c2: &Coordinate<f64>) -> f64 {
+    let (lat1, lat2) = (c1.y.to_radians(), c2.y.to_radians());
+    let sin_half_dlat = ((lat2 - lat1) / 2.0).sin();
+    let sin_half_dlng = ((c2.x.to_radians() - c1.x.to_radians()) / 2.0).sin();
+    let a = (lat1.cos() * lat2.cos()).mul_add(sin_half_dlng.powi(2), sin_half_dlat.powi(2));
+    2.0 * a.sqrt().min(1.0).asin()
+}
+
+/// The great circle distance between two coordinates in kilometers.
+///
+/// See [`great_circle_distance_rads`].
+pub fn great_circle_distance_km(c1: &Coordinate<f64>, c2: &Coordinate<f64>) -> f64 {
+    great_circle_distance_rads(c1, c2) * EARTH_RADIUS_KM
+}
+
+/// The great circle distance between two coordinates in meters.
+///
+/// See [`great_circle_distance_rads`].
+pub fn great_circle_distance_m(c1: &Coordinate<f64>, c2: &Coordinate<f64>) -> f64 {
+    great_circle_distance_km(c1, c2) * 1000.0
+}
+
 #[cfg(test)]
 mod tests {
     use geo::Coordinate;
     use geo_types::LineString;
 
-    use crate::{grid_path_cells, line, res0_cell_count, res0_cells, H3Cell};
+    use crate::{
+        great_circle_distance_km, great_circle_distance_m, great_circle_distance_rads,
+        grid_path_cells, line, pentagon_count, pentagons, res0_cell_count, res0_cells, H3Cell,
+        Index,
+    };
+
+    #[test]
+    fn great_circle_distance() {
+        let berlin = Coordinate::from((13.404954, 52.520008));
+        let paris = Coordinate::from((2.352222, 48.856614));
+        assert!((great_circle_distance_km(&berlin, &paris) - 877.5).abs() < 1.0);
+        assert!(
+            (great_circle_distance_m(&berlin, &paris)
+                - great_circle_distance_km(&berlin, &paris) * 1000.0)
+                .abs()
+                < 1e-6
+        );
+        assert!(great_circle_distance_rads(&berlin, &berlin).abs() < f64::EPSILON);
+
+        // antipodes
+        let c1 = Coordinate::from((0.0, 0.0));
+        let c2 = Coordinate::from((180.0, 0.0));
+        assert!((great_circle_distance_rads(&c1, &c2) - std::f64::consts::PI).abs() < 1e-12);
+    }
 
     #[test]
     fn line_across_multiple_faces() {
@@ -247,4 +330,18 @@ mod tests {
     fn test_res0_indexes() {
         assert_eq!(res0_cells().iter().count(), res0_cell_count() as usize);
     }
+
+    #[test]
+    fn test_pentagons() {
+        for h3_resolution in 0..=15 {
+            let cells = pentagons(h3_resolution).unwrap();
+            assert_eq!(cells.count(), pentagon_count() as usize);
+            for cell in cells.iter() {
+                assert!(cell.is_valid());
+                assert!(cell.is_pentagon());
+                assert_eq!(cell.resolution(), h3_resolution);
+            }
+        }
+        assert!(pentagons(16).is_err());
+    }
 }
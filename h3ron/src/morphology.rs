@@ -0,0 +1,304 @@
+//! Morphological operations on sets of cells.
+//!
+//! The operations are modeled after the morphological operations known from raster image
+//! processing, using the grid disk of radius `k` as the structuring element.
+//!
+//! All operations require the cells to be of the same resolution. The results
+//! do not depend on the iteration order of the input collections. With the `use-rayon`
+//! feature, the grid disks are computed in parallel.
+#[cfg(feature = "use-rayon")]
+use rayon::prelude::*;
+
+use geo_types::Polygon;
+
+use crate::collections::indexvec::IndexVec;
+#[cfg(feature = "roaring")]
+use crate::collections::H3Treemap;
+use crate::collections::{ContainsIndex, H3CellSet};
+use crate::error::check_same_resolution;
+use crate::iter::GridDiskBuilder;
+use crate::{
+    polygon_to_cells_with_containment, ContainmentMode, Error, H3Cell, Index, ToLinkedPolygons,
+};
+
+/// number of cells processed in one batch using the same [`GridDiskBuilder`].
+const CHUNK_SIZE: usize = 1000;
+
+/// Morphological operations on collections of cells.
+pub trait Morphology: Sized {
+    /// Dilation - grows `self` by all cells within the grid distance `k`.
+    fn dilate(&self, k: u32) -> Result<Self, Error>;
+
+    /// Erosion - shrinks `self` to the cells having their full grid disk of radius `k`
+    /// contained in `self`.
+    fn erode(&self, k: u32) -> Result<Self, Error>;
+
+    /// Opening - an erosion followed by a dilation. Removes parts narrower than `2 * k + 1` cells.
+    fn opening(&self, k: u32) -> Result<Self, Error> {
+        self.erode(k)?.dilate(k)
+    }
+
+    /// Closing - a dilation followed by an erosion. Closes gaps narrower than `2 * k + 1` cells.
+    fn closing(&self, k: u32) -> Result<Self, Error> {
+        self.dilate(k)?.erode(k)
+    }
+
+    /// Adds all cells enclosed by `self` to the collection.
+    fn fill_holes(&self) -> Result<Self, Error>;
+}
+
+impl Morphology for H3CellSet {
+    fn dilate(&self, k: u32) -> Result<Self, Error> {
+        let cells: Vec<_> = self.iter().copied().collect();
+        Ok(dilate_cells(&cells, k)?.into_iter().collect())
+    }
+
+    fn erode(&self, k: u32) -> Result<Self, Error> {
+        let cells: Vec<_> = self.iter().copied().collect();
+        Ok(erode_cells(&cells, self, k)?.into_iter().collect())
+    }
+
+    fn fill_holes(&self) -> Result<Self, Error> {
+        let cells: Vec<_> = self.iter().copied().collect();
+        Ok(fill_holes_cells(&cells)?.into_iter().collect())
+    }
+}
+
+/// The returned `IndexVec`s are sorted and free of duplicates.
+impl Morphology for IndexVec<H3Cell> {
+    fn dilate(&self, k: u32) -> Result<Self, Error> {
+        let cells: Vec<_> = self.iter().collect();
+        Ok(to_indexvec(dilate_cells(&cells, k)?))
+    }
+
+    fn erode(&self, k: u32) -> Result<Self, Error> {
+        let cellset: H3CellSet = self.iter().collect();
+        let cells: Vec<_> = cellset.iter().copied().collect();
+        Ok(to_indexvec(erode_cells(&cells, &cellset, k)?))
+    }
+
+    fn fill_holes(&self) -> Result<Self, Error> {
+        let cells: Vec<_> = self.iter().collect();
+        Ok(to_indexvec(fill_holes_cells(&cells)?))
+    }
+}
+
+#[cfg(feature = "roaring")]
+impl Morphology for H3Treemap<H3Cell> {
+    fn dilate(&self, k: u32) -> Result<Self, Error> {
+        let cells: Vec<_> = self.iter().collect();
+        Ok(Self::from_iter_with_sort(dilate_cells(&cells, k)?))
+    }
+
+    fn erode(&self, k: u32) -> Result<Self, Error> {
+        let cells: Vec<_> = self.iter().collect();
+        Ok(Self::from_iter_with_sort(erode_cells(&cells, self, k)?))
+    }
+
+    fn fill_holes(&self) -> Result<Self, Error> {
+        let cells: Vec<_> = self.iter().collect();
+        Ok(Self::from_iter_with_sort(fill_holes_cells(&cells)?))
+    }
+}
+
+fn to_indexvec(cells: Vec<H3Cell>) -> IndexVec<H3Cell> {
+    let mut index_vec = IndexVec::new();
+    for cell in cells {
+        index_vec.push(cell);
+    }
+    index_vec
+}
+
+/// Applies `f` to chunks of `cells` - in parallel when the `use-rayon` feature is enabled - and
+/// returns the sorted and deduplicated results.
+fn map_chunks<F>(cells: &[H3Cell], f: F) -> Result<Vec<H3Cell>, Error>
+where
+    F: Fn(&[H3Cell]) -> Result<Vec<H3Cell>, Error> + Send + Sync,
+{
+    #[cfg(feature = "use-rayon")]
+    let chunk_results = cells
+        .par_chunks(CHUNK_SIZE)
+        .map(f)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    #[cfg(not(feature = "use-rayon"))]
+    let chunk_results = cells
+        .chunks(CHUNK_SIZE)
+        .map(f)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut out: Vec<_> = chunk_results.into_iter().flatten().collect();
+    out.sort_unstable();
+    out.dedup();
+    Ok(out)
+}
+
+/// The resolution shared by all `cells` - `None` for empty `cells`.
+///
+/// Fails with [`Error::ResMismatch`] when the resolutions of `cells` differ.
+fn common_resolution(cells: &[H3Cell]) -> Result<Option<u8>, Error> {
+    match cells.split_first() {
+        Some((first, others)) => {
+            for cell in others {
+                check_same_resolution(*first, *cell)?;
+            }
+            Ok(Some(first.resolution()))
+        }
+        None => Ok(None),
+    }
+}
+
+fn dilate_cells(cells: &[H3Cell], k: u32) -> Result<Vec<H3Cell>, Error> {
+    common_resolution(cells)?;
+    map_chunks(cells, |chunk| {
+        let mut builder = GridDiskBuilder::create(0, k)?;
+        let mut out = Vec::with_capacity(chunk.len());
+        for cell in chunk {
+            out.extend(builder.build_grid_disk(cell)?.map(|(cell, _)| cell));
+        }
+        Ok(out)
+    })
+}
+
+fn erode_cells<C>(cells: &[H3Cell], contained: &C, k: u32) -> Result<Vec<H3Cell>, Error>
+where
+    C: ContainsIndex<H3Cell> + Sync,
+{
+    common_resolution(cells)?;
+    map_chunks(cells, |chunk| {
+        let mut builder = GridDiskBuilder::create(1, k)?;
+        let mut out = Vec::with_capacity(chunk.len());
+        for cell in chunk {
+            if builder
+                .build_grid_disk(cell)?
+                .all(|(neighbor, _)| contained.contains_index(&neighbor))
+            {
+                out.push(*cell);
+            }
+        }
+        Ok(out)
+    })
+}
+
+/// The holes are found using the interior rings of the linked polygons of `cells`.
+fn fill_holes_cells(cells: &[H3Cell]) -> Result<Vec<H3Cell>, Error> {
+    let h3_resolution = match common_resolution(cells)? {
+        Some(h3_resolution) => h3_resolution,
+        None => return Ok(vec![]),
+    };
+    let holes: Vec<_> = cells
+        .to_vec()
+        .to_linked_polygons(false)?
+        .into_iter()
+        .flat_map(|poly| poly.interiors().to_vec())
+        .map(|ring| Polygon::new(ring, vec![]))
+        .collect();
+
+    let fill = |hole: &Polygon<f64>| {
+        polygon_to_cells_with_containment(hole, h3_resolution, ContainmentMode::ContainsCentroid)
+            .map(Vec::from)
+    };
+
+    #[cfg(feature = "use-rayon")]
+    let filled = holes.par_iter().map(fill).collect::<Result<Vec<_>, _>>()?;
+
+    #[cfg(not(feature = "use-rayon"))]
+    let filled = holes.iter().map(fill).collect::<Result<Vec<_>, _>>()?;
+
+    let mut out = cells.to_vec();
+    out.extend(filled.into_iter().flatten());
+    out.sort_unstable();
+    out.dedup();
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collections::indexvec::IndexVec;
+    use crate::collections::H3CellSet;
+    use crate::H3Cell;
+
+    use super::Morphology;
+
+    fn center_cell() -> H3Cell {
+        H3Cell::try_from(0x89283080ddbffff_u64).unwrap()
+    }
+
+    fn disk(k: u32) -> H3CellSet {
+        center_cell().grid_disk(k).unwrap().iter().collect()
+    }
+
+    #[test]
+    fn dilate_and_erode() {
+        let single: H3CellSet = std::iter::once(center_cell()).collect();
+        assert_eq!(single.dilate(2).unwrap(), disk(2));
+        assert_eq!(single.dilate(0).unwrap(), single);
+
+        assert_eq!(disk(3).erode(2).unwrap(), disk(1));
+        assert_eq!(disk(2).erode(2).unwrap(), single);
+        assert!(disk(1).erode(2).unwrap().is_empty());
+    }
+
+    #[test]
+    fn opening_and_closing() {
+        // a disk with a single appended cell. opening removes the appendix
+        let appendix = center_cell().grid_ring(3).unwrap().first().unwrap();
+        let mut cells = disk(2);
+        cells.insert(appendix);
+        assert_eq!(cells.opening(1).unwrap(), disk(2));
+
+        // closing fills the missing center cell
+        let mut cells = disk(2);
+        cells.remove(&center_cell());
+        assert_eq!(cells.closing(1).unwrap(), disk(2));
+    }
+
+    #[test]
+    fn fill_holes() {
+        let mut cells = disk(3);
+        for cell in disk(1).iter() {
+            cells.remove(cell);
+        }
+        assert_eq!(cells.fill_holes().unwrap(), disk(3));
+
+        // no holes
+        assert_eq!(disk(2).fill_holes().unwrap(), disk(2));
+        assert!(H3CellSet::default().fill_holes().unwrap().is_empty());
+    }
+
+    #[test]
+    fn mixed_resolutions() {
+        let mut cells = disk(1);
+        cells.insert(center_cell().get_parent(5).unwrap());
+        assert!(cells.dilate(1).is_err());
+    }
+
+    #[test]
+    fn indexvec_matches_cellset() {
+        let mut index_vec = IndexVec::new();
+        for cell in disk(2).iter() {
+            index_vec.push(*cell);
+        }
+        let dilated: Vec<_> = index_vec.dilate(1).unwrap().into();
+        let mut expected: Vec<_> = disk(3).into_iter().collect();
+        expected.sort_unstable();
+        assert_eq!(dilated, expected);
+
+        let eroded: Vec<_> = index_vec.erode(1).unwrap().into();
+        let mut expected: Vec<_> = disk(1).into_iter().collect();
+        expected.sort_unstable();
+        assert_eq!(eroded, expected);
+    }
+
+    #[cfg(feature = "roaring")]
+    #[test]
+    fn treemap_matches_cellset() {
+        use crate::collections::H3Treemap;
+
+        let treemap: H3Treemap<H3Cell> = disk(2).iter().collect();
+        let dilated = treemap.dilate(1).unwrap();
+        assert_eq!(dilated.iter().collect::<H3CellSet>(), disk(3));
+        let eroded = treemap.erode(1).unwrap();
+        assert_eq!(eroded.iter().collect::<H3CellSet>(), disk(1));
+    }
+}
@@ -2,13 +2,17 @@ use crate::collections::H3CellMap;
 use std::os::raw::c_int;
 
 use geo::algorithm::euclidean_distance::EuclideanDistance;
-use geo_types::{Coordinate, LineString, MultiLineString, Point, Polygon};
+use geo_types::{Coordinate, LineString, MultiLineString, MultiPolygon, Point, Polygon};
 
 use h3ron_h3_sys::H3Index;
 
 use crate::algorithm::smoothen_h3_linked_polygon;
+use crate::antimeridian::{
+    denormalize_polygon, normalize_polygon, split_polygon, AntimeridianHandling,
+};
 use crate::collections::indexvec::IndexVec;
-use crate::collections::CompactedCellVec;
+use crate::collections::{CompactedCellVec, H3CellSet};
+use crate::topology::{join_to_rings, rings_to_polygons};
 use crate::{Error, H3Cell};
 
 pub trait ToPolygon {
@@ -35,11 +39,55 @@ pub trait ToMultiLineString {
     fn to_multilinestring(&self) -> Result<MultiLineString<f64>, Self::Error>;
 }
 
+/// Polygon of a cell with the given handling of the antimeridian.
+pub trait ToAntimeridianPolygon {
+    type Error;
+
+    fn to_antimeridian_polygon(
+        &self,
+        antimeridian: AntimeridianHandling,
+    ) -> Result<MultiPolygon<f64>, Self::Error>;
+}
+
+impl ToAntimeridianPolygon for H3Cell {
+    type Error = Error;
+
+    fn to_antimeridian_polygon(
+        &self,
+        antimeridian: AntimeridianHandling,
+    ) -> Result<MultiPolygon<f64>, Self::Error> {
+        let poly = self.to_polygon()?;
+        Ok(match antimeridian {
+            AntimeridianHandling::None => MultiPolygon(vec![poly]),
+            AntimeridianHandling::Normalize => MultiPolygon(vec![normalize_polygon(&poly)]),
+            AntimeridianHandling::Split => split_polygon(&poly),
+        })
+    }
+}
+
 /// join hexagon polygons to larger polygons where hexagons are touching each other
 pub trait ToLinkedPolygons {
     type Error;
 
     fn to_linked_polygons(&self, smoothen: bool) -> Result<Vec<Polygon<f64>>, Self::Error>;
+
+    /// Variant of [`ToLinkedPolygons::to_linked_polygons`] with handling of polygons
+    /// crossing the antimeridian.
+    ///
+    /// The default implementation builds the polygons using
+    /// [`ToLinkedPolygons::to_linked_polygons`] without smoothing and applies the handling
+    /// like [`to_linked_polygons_with_antimeridian`] does.
+    fn to_linked_polygons_with_antimeridian(
+        &self,
+        smoothen: bool,
+        antimeridian: AntimeridianHandling,
+    ) -> Result<Vec<Polygon<f64>>, Self::Error> {
+        Ok(apply_antimeridian_handling(
+            self.to_linked_polygons(false)?,
+            smoothen,
+            antimeridian,
+        ))
+    }
 }
 
 impl ToLinkedPolygons for Vec<H3Cell> {
@@ -64,21 +112,47 @@ impl ToLinkedPolygons for IndexVec<H3Cell> {
     }
 }
 
+impl ToLinkedPolygons for H3CellSet {
+    type Error = Error;
+
+    fn to_linked_polygons(&self, smoothen: bool) -> Result<Vec<Polygon<f64>>, Self::Error> {
+        let mut cells = self.iter().copied().collect::<Vec<_>>();
+        cells.sort_unstable();
+        to_linked_polygons(&cells, smoothen)
+    }
+}
+
+/// The polygons are built directly from the compacted cells without uncompacting all of them, so
+/// this works with large coverages of mixed resolutions. The boundaries of coarser cells
+/// get stitched to finer cells at the finest resolution contained.
 impl ToLinkedPolygons for CompactedCellVec {
     type Error = Error;
 
     fn to_linked_polygons(&self, smoothen: bool) -> Result<Vec<Polygon<f64>>, Self::Error> {
-        match self.finest_resolution_contained() {
-            Some(resolution) => {
-                let mut cells: Vec<_> = self
-                    .iter_uncompacted_cells(resolution)
-                    .collect::<Result<Vec<_>, _>>()?;
-                cells.sort_unstable();
-                cells.dedup();
-                to_linked_polygons(&cells, smoothen)
-            }
-            None => Ok(Vec::new()),
-        }
+        self.to_linked_polygons_with_antimeridian(smoothen, AntimeridianHandling::None)
+    }
+
+    /// The rings are always normalised before being assembled to polygons, so the orientation
+    /// of the rings is determined correctly and interior rings get assigned to their exterior
+    /// rings also when crossing the antimeridian.
+    fn to_linked_polygons_with_antimeridian(
+        &self,
+        smoothen: bool,
+        antimeridian: AntimeridianHandling,
+    ) -> Result<Vec<Polygon<f64>>, Self::Error> {
+        let rings = join_to_rings(&self.boundary_directed_edges()?)?
+            .into_iter()
+            .map(|ring| {
+                normalize_polygon(&Polygon::new(ring, vec![]))
+                    .exterior()
+                    .clone()
+            })
+            .collect();
+        Ok(apply_antimeridian_handling(
+            rings_to_polygons(rings)?,
+            smoothen,
+            antimeridian,
+        ))
     }
 }
 
@@ -99,6 +173,24 @@ pub trait ToAlignedLinkedPolygons {
         align_to_h3_resolution: u8,
         smoothen: bool,
     ) -> Result<Vec<Polygon<f64>>, Self::Error>;
+
+    /// Variant of [`ToAlignedLinkedPolygons::to_aligned_linked_polygons`] with
+    /// handling of polygons crossing the antimeridian.
+    ///
+    /// The default implementation applies the handling to the polygons returned by
+    /// [`ToAlignedLinkedPolygons::to_aligned_linked_polygons`].
+    fn to_aligned_linked_polygons_with_antimeridian(
+        &self,
+        align_to_h3_resolution: u8,
+        smoothen: bool,
+        antimeridian: AntimeridianHandling,
+    ) -> Result<Vec<Polygon<f64>>, Self::Error> {
+        Ok(apply_antimeridian_handling(
+            self.to_aligned_linked_polygons(align_to_h3_resolution, smoothen)?,
+            false,
+            antimeridian,
+        ))
+    }
 }
 
 impl ToAlignedLinkedPolygons for Vec<H3Cell> {
@@ -108,6 +200,21 @@ impl ToAlignedLinkedPolygons for Vec<H3Cell> {
         &self,
         align_to_h3_resolution: u8,
         smoothen: bool,
+    ) -> Result<Vec<Polygon<f64>>, Self::Error> {
+        self.to_aligned_linked_polygons_with_antimeridian(
+            align_to_h3_resolution,
+            smoothen,
+            AntimeridianHandling::None,
+        )
+    }
+
+    /// The alignment to the corners of the parent cells is performed on the normalised
+    /// polygons.
+    fn to_aligned_linked_polygons_with_antimeridian(
+        &self,
+        align_to_h3_resolution: u8,
+        smoothen: bool,
+        antimeridian: AntimeridianHandling,
     ) -> Result<Vec<Polygon<f64>>, Self::Error> {
         let mut cells_grouped = H3CellMap::default();
         for cell in self.iter() {
@@ -125,8 +232,7 @@ impl ToAlignedLinkedPolygons for Vec<H3Cell> {
                 // align to the corners of the parent index
                 //
 
-                let parent_poly_vertices: Vec<_> = parent_cell
-                    .to_polygon()?
+                let parent_poly_vertices: Vec<_> = normalize_polygon(&parent_cell.to_polygon()?)
                     .exterior()
                     .0
                     .iter()
@@ -141,7 +247,13 @@ impl ToAlignedLinkedPolygons for Vec<H3Cell> {
                     p1.euclidean_distance(&p2)
                 };
 
-                for poly in to_linked_polygons(&cells, true)?.drain(..) {
+                for poly in to_linked_polygons_with_antimeridian(
+                    &cells,
+                    true,
+                    AntimeridianHandling::Normalize,
+                )?
+                .drain(..)
+                {
                     let points_new: Vec<_> = poly
                         .exterior()
                         .0
@@ -160,13 +272,63 @@ impl ToAlignedLinkedPolygons for Vec<H3Cell> {
                     ));
                 }
             } else {
-                polygons.append(&mut to_linked_polygons(&cells, false)?);
+                polygons.append(&mut to_linked_polygons_with_antimeridian(
+                    &cells,
+                    false,
+                    AntimeridianHandling::Normalize,
+                )?);
             }
         }
-        Ok(polygons)
+        Ok(apply_antimeridian_handling(polygons, false, antimeridian))
     }
 }
 
+/// convert cells to linked polygons with handling of polygons crossing the antimeridian.
+///
+/// Smoothing is applied after normalising the polygons and before splitting them, so
+/// polygons crossing the antimeridian get smoothened correctly.
+///
+/// for this case, the slice must already be deduplicated, and all h3 cells must be the same resolutions
+pub fn to_linked_polygons_with_antimeridian(
+    cells: &[H3Cell],
+    smoothen: bool,
+    antimeridian: AntimeridianHandling,
+) -> Result<Vec<Polygon<f64>>, Error> {
+    Ok(apply_antimeridian_handling(
+        to_linked_polygons(cells, false)?,
+        smoothen,
+        antimeridian,
+    ))
+}
+
+/// Applies the `antimeridian` handling to linked polygons which have not been smoothened yet.
+///
+/// The polygons get normalised first, so the smoothing also works for polygons crossing the
+/// antimeridian. With [`AntimeridianHandling::None`] the longitudes get wrapped back to
+/// `[-180, 180]` afterwards, with [`AntimeridianHandling::Split`] the polygons get split.
+fn apply_antimeridian_handling(
+    polygons: Vec<Polygon<f64>>,
+    smoothen: bool,
+    antimeridian: AntimeridianHandling,
+) -> Vec<Polygon<f64>> {
+    polygons
+        .iter()
+        .map(|poly| {
+            let normalized = normalize_polygon(poly);
+            if smoothen {
+                smoothen_h3_linked_polygon(&normalized)
+            } else {
+                normalized
+            }
+        })
+        .flat_map(|poly| match antimeridian {
+            AntimeridianHandling::None => vec![denormalize_polygon(&poly)],
+            AntimeridianHandling::Normalize => vec![poly],
+            AntimeridianHandling::Split => split_polygon(&poly).0,
+        })
+        .collect()
+}
+
 /// convert cells to linked polygons
 ///
 /// With `smoothen` an optional smoothing can be applied to the polygons to remove
@@ -239,9 +401,14 @@ pub fn to_linked_polygons(cells: &[H3Cell], smoothen: bool) -> Result<Vec<Polygo
 
 #[cfg(test)]
 mod tests {
-    use geo_types::Coordinate;
+    use geo_types::{Coordinate, Polygon};
 
-    use crate::{H3Cell, ToLinkedPolygons};
+    use geo::algorithm::area::Area;
+    use geo::algorithm::coords_iter::CoordsIter;
+
+    use crate::antimeridian::{crosses_antimeridian, AntimeridianHandling};
+    use crate::collections::CompactedCellVec;
+    use crate::{H3Cell, ToAlignedLinkedPolygons, ToAntimeridianPolygon, ToLinkedPolygons};
 
     #[test]
     fn donut_linked_polygon() {
@@ -255,4 +422,150 @@ mod tests {
         assert_eq!(polygons[0].interiors().len(), 1);
         assert_eq!(polygons[0].interiors()[0].0.len(), 7);
     }
+
+    fn antimeridian_disk() -> Vec<H3Cell> {
+        H3Cell::from_coordinate(Coordinate::from((180.0, 10.0)), 5)
+            .unwrap()
+            .grid_disk(2)
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn cell_polygon_antimeridian() {
+        let cell = H3Cell::from_coordinate(Coordinate::from((180.0, 10.0)), 3).unwrap();
+        let mpoly = cell
+            .to_antimeridian_polygon(AntimeridianHandling::None)
+            .unwrap();
+        assert_eq!(mpoly.0.len(), 1);
+        assert!(crosses_antimeridian(&mpoly.0[0]));
+
+        let mpoly = cell
+            .to_antimeridian_polygon(AntimeridianHandling::Normalize)
+            .unwrap();
+        assert_eq!(mpoly.0.len(), 1);
+        assert!(!crosses_antimeridian(&mpoly.0[0]));
+
+        let mpoly = cell
+            .to_antimeridian_polygon(AntimeridianHandling::Split)
+            .unwrap();
+        assert_eq!(mpoly.0.len(), 2);
+    }
+
+    #[test]
+    fn linked_polygons_antimeridian() {
+        let cells = antimeridian_disk();
+        let polygons = cells.to_linked_polygons(false).unwrap();
+        assert_eq!(polygons.len(), 1);
+        assert!(crosses_antimeridian(&polygons[0]));
+
+        let normalized = cells
+            .to_linked_polygons_with_antimeridian(false, AntimeridianHandling::Normalize)
+            .unwrap();
+        assert_eq!(normalized.len(), 1);
+        assert!(!crosses_antimeridian(&normalized[0]));
+
+        for smoothen in [false, true] {
+            let split = cells
+                .to_linked_polygons_with_antimeridian(smoothen, AntimeridianHandling::Split)
+                .unwrap();
+            assert_eq!(split.len(), 2);
+            assert!(split
+                .iter()
+                .all(|poly| poly.coords_iter().all(|c| (-180.0..=180.0).contains(&c.x))));
+            if !smoothen {
+                let area: f64 = split.iter().map(|poly| poly.unsigned_area()).sum();
+                assert!((area - normalized[0].unsigned_area()).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn aligned_linked_polygons_antimeridian() {
+        let cells = antimeridian_disk();
+        for smoothen in [false, true] {
+            let polygons = cells
+                .to_aligned_linked_polygons_with_antimeridian(
+                    3,
+                    smoothen,
+                    AntimeridianHandling::Split,
+                )
+                .unwrap();
+            assert!(!polygons.is_empty());
+            for poly in polygons.iter() {
+                assert!(!crosses_antimeridian(poly));
+                assert!(poly.coords_iter().all(|c| (-180.0..=180.0).contains(&c.x)));
+            }
+        }
+    }
+
+    /// a ring of res 5 cells around a hole containing a single res 7 cell
+    fn mixed_resolution_cells() -> CompactedCellVec {
+        let center = H3Cell::from_coordinate(Coordinate::from((23.3, 12.3)), 5).unwrap();
+        let mut cells: Vec<_> = center.grid_ring_unsafe(1).unwrap().iter().collect();
+        cells.push(center.center_child(7).unwrap());
+        let mut ccv = CompactedCellVec::new();
+        ccv.add_cells(cells, false).unwrap();
+        ccv
+    }
+
+    #[test]
+    fn compacted_linked_polygons_mixed_resolutions() {
+        let ccv = mixed_resolution_cells();
+        let polygons = ccv.to_linked_polygons(false).unwrap();
+        assert_eq!(polygons.len(), 2);
+        assert_eq!(
+            polygons
+                .iter()
+                .map(|poly| poly.interiors().len())
+                .sum::<usize>(),
+            1
+        );
+
+        // compare with the polygons of the uncompacted cells
+        let uncompacted: Vec<_> = ccv
+            .iter_uncompacted_cells(7)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let expected = uncompacted.to_linked_polygons(false).unwrap();
+        assert_eq!(polygons.len(), expected.len());
+        let area = |polys: &[Polygon<f64>]| polys.iter().map(Area::unsigned_area).sum::<f64>();
+        assert!((area(&polygons) - area(&expected)).abs() / area(&expected) < 1e-9);
+        assert_eq!(
+            polygons.iter().map(CoordsIter::coords_count).sum::<usize>(),
+            expected.iter().map(CoordsIter::coords_count).sum::<usize>()
+        );
+    }
+
+    #[test]
+    fn compacted_linked_polygons_antimeridian() {
+        let cells = antimeridian_disk();
+        let mut ccv = CompactedCellVec::new();
+        ccv.add_cells(cells.clone(), false).unwrap();
+
+        let polygons = ccv.to_linked_polygons(false).unwrap();
+        let expected = cells.to_linked_polygons(false).unwrap();
+        assert_eq!(polygons.len(), expected.len());
+        assert!(crosses_antimeridian(&polygons[0]));
+        assert!(polygons[0].interiors().is_empty());
+
+        let normalized = ccv
+            .to_linked_polygons_with_antimeridian(false, AntimeridianHandling::Normalize)
+            .unwrap();
+        assert_eq!(normalized.len(), 1);
+        assert!(!crosses_antimeridian(&normalized[0]));
+
+        let split = ccv
+            .to_linked_polygons_with_antimeridian(true, AntimeridianHandling::Split)
+            .unwrap();
+        assert_eq!(split.len(), 2);
+    }
+
+    #[test]
+    fn compacted_linked_polygons_empty() {
+        assert!(CompactedCellVec::new()
+            .to_linked_polygons(true)
+            .unwrap()
+            .is_empty());
+    }
 }
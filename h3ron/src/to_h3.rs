@@ -1,34 +1,189 @@
+use geo::algorithm::bounding_rect::BoundingRect;
+use geo::algorithm::contains::Contains;
+use geo::algorithm::haversine_distance::HaversineDistance;
+use geo::algorithm::intersects::Intersects;
+use geo::algorithm::translate::Translate;
 use geo::{
     GeometryCollection, LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Rect,
     Triangle,
 };
 use geo_types::{Coordinate, Geometry, Line, Polygon};
 
+use crate::antimeridian::{normalize_polygon, split_polygon};
 use crate::collections::indexvec::IndexVec;
+use crate::collections::H3CellSet;
 use crate::error::check_valid_h3_resolution;
-use crate::{line, polygon_to_cells, Error, H3Cell, Index};
+use crate::{line, polygon_to_cells, Error, H3Cell, H3DirectedEdge, Index, ToPolygon};
 use std::convert::TryInto;
 
+/// Selects which cells are returned when converting areal geometries to cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ContainmentMode {
+    /// Cells with their centroid within the geometry. This is the behaviour
+    /// of the `polygonToCells` function of libh3.
+    #[default]
+    ContainsCentroid,
+
+    /// Cells which are fully contained within the geometry.
+    FullyContained,
+
+    /// Cells which overlap with the geometry.
+    Overlapping,
+
+    /// Cells with a bounding box overlapping with the geometry.
+    OverlappingBoundingBox,
+}
+
 /// convert to indexes at the given resolution
 ///
 /// The output vec may contain duplicate indexes in case of
 /// overlapping input geometries.
 pub trait ToH3Cells {
     fn to_h3_cells(&self, h3_resolution: u8) -> Result<IndexVec<H3Cell>, Error>;
+
+    /// convert to indexes at the given resolution using the given [`ContainmentMode`].
+    ///
+    /// The `containment_mode` only affects areal geometries. All other geometries
+    /// return the same cells as [`ToH3Cells::to_h3_cells`].
+    fn to_h3_cells_with_containment(
+        &self,
+        h3_resolution: u8,
+        containment_mode: ContainmentMode,
+    ) -> Result<IndexVec<H3Cell>, Error> {
+        let _ = containment_mode;
+        self.to_h3_cells(h3_resolution)
+    }
+}
+
+/// decides if a cell polygon along the rings of a polygon gets selected
+type BoundaryCellPredicate = fn(&Polygon<f64>, &Polygon<f64>) -> bool;
+
+/// Convert a polygon to cells using the given [`ContainmentMode`].
+///
+/// With [`ContainmentMode::ContainsCentroid`] this is the same as [`polygon_to_cells`]. The
+/// other modes extend the result of [`polygon_to_cells`] by inspecting all cells along the
+/// rings of the polygon.
+///
+/// Polygons crossing the antimeridian - either by containing longitude jumps of more than 180°
+/// or by using longitudes outside of `[-180, 180]` - are split at the antimeridian before
+/// being passed to libh3.
+pub fn polygon_to_cells_with_containment(
+    poly: &Polygon<f64>,
+    h3_resolution: u8,
+    containment_mode: ContainmentMode,
+) -> Result<IndexVec<H3Cell>, Error> {
+    check_valid_h3_resolution(h3_resolution)?;
+    let parts = split_polygon(poly).0;
+    if containment_mode == ContainmentMode::ContainsCentroid && parts.len() == 1 {
+        return polygon_to_cells(&parts[0], h3_resolution);
+    }
+
+    let selects: Option<BoundaryCellPredicate> = match containment_mode {
+        ContainmentMode::ContainsCentroid => None,
+        ContainmentMode::FullyContained => Some(|poly, cell_poly| poly.contains(cell_poly)),
+        ContainmentMode::Overlapping => Some(|poly, cell_poly| poly.intersects(cell_poly)),
+        ContainmentMode::OverlappingBoundingBox => Some(|poly, cell_poly| {
+            cell_poly
+                .bounding_rect()
+                .map(|rect| poly.intersects(&rect.to_polygon()))
+                .unwrap_or_default()
+        }),
+    };
+
+    let mut cells = H3CellSet::default();
+    let mut boundary_cells = H3CellSet::default();
+    for part in parts.iter() {
+        cells.extend(polygon_to_cells(part, h3_resolution)?.iter());
+        if selects.is_some() {
+            boundary_cells.extend(cells_along_rings(part, h3_resolution)?);
+        }
+    }
+
+    if let Some(selects) = selects {
+        // cells with the centroid within the polygon which are not touched by any ring
+        // are fully contained.
+        cells.retain(|cell| !boundary_cells.contains(cell));
+
+        // the cells get compared against the normalized polygon, so polygons split at the
+        // antimeridian are handled correctly.
+        let reference_poly = normalize_polygon(poly);
+        for cell in boundary_cells {
+            let cell_poly = normalize_polygon(&cell.to_polygon()?);
+            if [0.0, 360.0]
+                .iter()
+                .any(|x_offset| selects(&reference_poly, &cell_poly.translate(*x_offset, 0.0)))
+            {
+                cells.insert(cell);
+            }
+        }
+    }
+
+    let mut index_vec = IndexVec::new();
+    for cell in cells {
+        index_vec.push(cell);
+    }
+    index_vec.sort_unstable();
+    Ok(index_vec)
+}
+
+/// All cells touched by the rings of the polygon, including their direct neighbors.
+///
+/// The rings are densified to a spacing of a quarter of the average edge length, so the
+/// cells containing the densified points and their neighbors are guaranteed to cover
+/// all cells intersected by the rings.
+fn cells_along_rings(poly: &Polygon<f64>, h3_resolution: u8) -> Result<H3CellSet, Error> {
+    let step_m = H3DirectedEdge::edge_length_avg_m(h3_resolution)? / 4.0;
+
+    let mut ring_cells = H3CellSet::default();
+    for ring in std::iter::once(poly.exterior()).chain(poly.interiors().iter()) {
+        for coords in ring.0.windows(2) {
+            let distance_m = Point::from(coords[0]).haversine_distance(&Point::from(coords[1]));
+            let num_steps = (distance_m / step_m).ceil().max(1.0) as usize;
+            for step in 0..=num_steps {
+                let fraction = step as f64 / num_steps as f64;
+                let coordinate = Coordinate {
+                    x: (coords[1].x - coords[0].x).mul_add(fraction, coords[0].x),
+                    y: (coords[1].y - coords[0].y).mul_add(fraction, coords[0].y),
+                };
+                ring_cells.insert(H3Cell::from_coordinate(coordinate, h3_resolution)?);
+            }
+        }
+    }
+
+    let mut cells = H3CellSet::default();
+    for ring_cell in ring_cells {
+        cells.extend(ring_cell.grid_disk(1)?.iter());
+    }
+    Ok(cells)
 }
 
 impl ToH3Cells for Polygon<f64> {
     fn to_h3_cells(&self, h3_resolution: u8) -> Result<IndexVec<H3Cell>, Error> {
-        check_valid_h3_resolution(h3_resolution)?;
-        polygon_to_cells(self, h3_resolution)
+        polygon_to_cells_with_containment(self, h3_resolution, ContainmentMode::ContainsCentroid)
+    }
+
+    fn to_h3_cells_with_containment(
+        &self,
+        h3_resolution: u8,
+        containment_mode: ContainmentMode,
+    ) -> Result<IndexVec<H3Cell>, Error> {
+        polygon_to_cells_with_containment(self, h3_resolution, containment_mode)
     }
 }
 
 impl ToH3Cells for MultiPolygon<f64> {
     fn to_h3_cells(&self, h3_resolution: u8) -> Result<IndexVec<H3Cell>, Error> {
+        self.to_h3_cells_with_containment(h3_resolution, ContainmentMode::ContainsCentroid)
+    }
+
+    fn to_h3_cells_with_containment(
+        &self,
+        h3_resolution: u8,
+        containment_mode: ContainmentMode,
+    ) -> Result<IndexVec<H3Cell>, Error> {
         let mut outvec = IndexVec::new();
         for poly in &self.0 {
-            let mut thisvec = poly.to_h3_cells(h3_resolution)?;
+            let mut thisvec = poly.to_h3_cells_with_containment(h3_resolution, containment_mode)?;
             outvec.append(&mut thisvec);
         }
         Ok(outvec)
@@ -80,12 +235,30 @@ impl ToH3Cells for Rect<f64> {
     fn to_h3_cells(&self, h3_resolution: u8) -> Result<IndexVec<H3Cell>, Error> {
         self.to_polygon().to_h3_cells(h3_resolution)
     }
+
+    fn to_h3_cells_with_containment(
+        &self,
+        h3_resolution: u8,
+        containment_mode: ContainmentMode,
+    ) -> Result<IndexVec<H3Cell>, Error> {
+        self.to_polygon()
+            .to_h3_cells_with_containment(h3_resolution, containment_mode)
+    }
 }
 
 impl ToH3Cells for Triangle<f64> {
     fn to_h3_cells(&self, h3_resolution: u8) -> Result<IndexVec<H3Cell>, Error> {
         self.to_polygon().to_h3_cells(h3_resolution)
     }
+
+    fn to_h3_cells_with_containment(
+        &self,
+        h3_resolution: u8,
+        containment_mode: ContainmentMode,
+    ) -> Result<IndexVec<H3Cell>, Error> {
+        self.to_polygon()
+            .to_h3_cells_with_containment(h3_resolution, containment_mode)
+    }
 }
 
 impl ToH3Cells for Line<f64> {
@@ -96,9 +269,17 @@ impl ToH3Cells for Line<f64> {
 
 impl ToH3Cells for GeometryCollection<f64> {
     fn to_h3_cells(&self, h3_resolution: u8) -> Result<IndexVec<H3Cell>, Error> {
+        self.to_h3_cells_with_containment(h3_resolution, ContainmentMode::ContainsCentroid)
+    }
+
+    fn to_h3_cells_with_containment(
+        &self,
+        h3_resolution: u8,
+        containment_mode: ContainmentMode,
+    ) -> Result<IndexVec<H3Cell>, Error> {
         let mut outvec = IndexVec::new();
         for geom in &self.0 {
-            let mut thisvec = geom.to_h3_cells(h3_resolution)?;
+            let mut thisvec = geom.to_h3_cells_with_containment(h3_resolution, containment_mode)?;
             outvec.append(&mut thisvec);
         }
         Ok(outvec)
@@ -107,17 +288,165 @@ impl ToH3Cells for GeometryCollection<f64> {
 
 impl ToH3Cells for Geometry<f64> {
     fn to_h3_cells(&self, h3_resolution: u8) -> Result<IndexVec<H3Cell>, Error> {
+        self.to_h3_cells_with_containment(h3_resolution, ContainmentMode::ContainsCentroid)
+    }
+
+    fn to_h3_cells_with_containment(
+        &self,
+        h3_resolution: u8,
+        containment_mode: ContainmentMode,
+    ) -> Result<IndexVec<H3Cell>, Error> {
         match self {
             Geometry::Point(pt) => pt.to_h3_cells(h3_resolution),
             Geometry::Line(l) => l.to_h3_cells(h3_resolution),
             Geometry::LineString(ls) => ls.to_h3_cells(h3_resolution),
-            Geometry::Polygon(poly) => poly.to_h3_cells(h3_resolution),
+            Geometry::Polygon(poly) => {
+                poly.to_h3_cells_with_containment(h3_resolution, containment_mode)
+            }
             Geometry::MultiPoint(mp) => mp.to_h3_cells(h3_resolution),
             Geometry::MultiLineString(mls) => mls.to_h3_cells(h3_resolution),
-            Geometry::MultiPolygon(mpoly) => mpoly.to_h3_cells(h3_resolution),
-            Geometry::GeometryCollection(gc) => gc.to_h3_cells(h3_resolution),
-            Geometry::Rect(r) => r.to_h3_cells(h3_resolution),
-            Geometry::Triangle(tr) => tr.to_h3_cells(h3_resolution),
+            Geometry::MultiPolygon(mpoly) => {
+                mpoly.to_h3_cells_with_containment(h3_resolution, containment_mode)
+            }
+            Geometry::GeometryCollection(gc) => {
+                gc.to_h3_cells_with_containment(h3_resolution, containment_mode)
+            }
+            Geometry::Rect(r) => r.to_h3_cells_with_containment(h3_resolution, containment_mode),
+            Geometry::Triangle(tr) => {
+                tr.to_h3_cells_with_containment(h3_resolution, containment_mode)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use geo::{polygon, Rect};
+    use geo_types::{Coordinate, Geometry, GeometryCollection, MultiPolygon, Polygon};
+
+    use crate::collections::H3CellSet;
+    use crate::to_h3::{ContainmentMode, ToH3Cells};
+    use crate::ToCoordinate;
+
+    fn cellset<T: ToH3Cells>(geom: &T, containment_mode: ContainmentMode) -> H3CellSet {
+        geom.to_h3_cells_with_containment(7, containment_mode)
+            .unwrap()
+            .iter()
+            .collect()
+    }
+
+    fn thin_polygon() -> Polygon<f64> {
+        polygon!(
+            (x: 10.0, y: 50.0),
+            (x: 10.3, y: 50.0),
+            (x: 10.3, y: 50.0005),
+            (x: 10.0, y: 50.0005),
+            (x: 10.0, y: 50.0),
+        )
+    }
+
+    #[test]
+    fn containment_modes_are_nested() {
+        let poly = Rect::new(
+            Coordinate { x: 10.0, y: 50.0 },
+            Coordinate { x: 10.2, y: 50.1 },
+        )
+        .to_polygon();
+
+        let contained = cellset(&poly, ContainmentMode::FullyContained);
+        let centroid = cellset(&poly, ContainmentMode::ContainsCentroid);
+        let overlapping = cellset(&poly, ContainmentMode::Overlapping);
+        let overlapping_bbox = cellset(&poly, ContainmentMode::OverlappingBoundingBox);
+
+        assert!(!contained.is_empty());
+        assert!(contained.len() < centroid.len());
+        assert!(centroid.len() < overlapping.len());
+        assert!(overlapping.len() <= overlapping_bbox.len());
+        assert!(contained.is_subset(&centroid));
+        assert!(centroid.is_subset(&overlapping));
+        assert!(overlapping.is_subset(&overlapping_bbox));
+    }
+
+    #[test]
+    fn thin_polygon_overlapping() {
+        let poly = thin_polygon();
+        assert!(cellset(&poly, ContainmentMode::FullyContained).is_empty());
+
+        let overlapping = cellset(&poly, ContainmentMode::Overlapping);
+        assert!(overlapping.len() > cellset(&poly, ContainmentMode::ContainsCentroid).len());
+
+        // the cells along the centerline of the polygon must all be included
+        let centerline: geo::LineString<f64> = vec![(10.0, 50.00025), (10.3, 50.00025)].into();
+        for cell in centerline.to_h3_cells(7).unwrap().iter() {
+            assert!(overlapping.contains(&cell));
         }
     }
+
+    #[test]
+    fn containment_mode_for_collections() {
+        let poly = thin_polygon();
+        let expected = cellset(&poly, ContainmentMode::Overlapping);
+
+        let mpoly = MultiPolygon(vec![poly.clone()]);
+        assert_eq!(cellset(&mpoly, ContainmentMode::Overlapping), expected);
+
+        let gc = GeometryCollection(vec![Geometry::Polygon(poly)]);
+        assert_eq!(cellset(&gc, ContainmentMode::Overlapping), expected);
+
+        let rect = Rect::new(
+            Coordinate { x: 10.0, y: 50.0 },
+            Coordinate {
+                x: 10.3,
+                y: 50.0005,
+            },
+        );
+        assert_eq!(cellset(&rect, ContainmentMode::Overlapping), expected);
+        assert_eq!(
+            cellset(&Geometry::Rect(rect), ContainmentMode::Overlapping),
+            expected
+        );
+    }
+
+    fn rect_cells(min_x: f64, max_x: f64) -> H3CellSet {
+        let rect = Rect::new(
+            Coordinate::from((min_x, 10.0)),
+            Coordinate::from((max_x, 12.0)),
+        );
+        cellset(&rect, ContainmentMode::ContainsCentroid)
+    }
+
+    #[test]
+    fn antimeridian_crossing_rect() {
+        let expected: H3CellSet = rect_cells(178.0, 180.0)
+            .union(&rect_cells(-180.0, -178.0))
+            .copied()
+            .collect();
+        assert!(!expected.is_empty());
+
+        // longitudes exceeding 180°
+        assert_eq!(rect_cells(178.0, 182.0), expected);
+        // longitudes below -180°
+        assert_eq!(rect_cells(-182.0, -178.0), expected);
+    }
+
+    #[test]
+    fn antimeridian_crossing_polygon() {
+        let expected = rect_cells(178.0, 182.0);
+        let poly = polygon!(
+            (x: 178.0, y: 10.0),
+            (x: -178.0, y: 10.0),
+            (x: -178.0, y: 12.0),
+            (x: 178.0, y: 12.0),
+            (x: 178.0, y: 10.0),
+        );
+        assert_eq!(cellset(&poly, ContainmentMode::ContainsCentroid), expected);
+
+        let overlapping = cellset(&poly, ContainmentMode::Overlapping);
+        assert!(overlapping.is_superset(&expected));
+        assert!(overlapping.len() > expected.len());
+        assert!(overlapping.iter().all(|cell| {
+            let c = cell.to_coordinate().unwrap();
+            c.x.abs() > 170.0 && c.y > 9.0 && c.y < 13.0
+        }));
+    }
 }
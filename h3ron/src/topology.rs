@@ -0,0 +1,383 @@
+//! Topological relations within collections of cells.
+use std::cmp::Ordering;
+
+use geo::algorithm::contains::Contains;
+use geo_types::{Coordinate, LineString, MultiLineString, Point, Polygon};
+
+use crate::collections::{H3CellSet, HashMap};
+use crate::iter::GridDiskBuilder;
+use crate::{Error, H3Cell, H3DirectedEdge, H3Vertex};
+
+/// Defines which cells are considered to be adjacent to each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Adjacency {
+    /// Cells sharing an edge are adjacent.
+    #[default]
+    Edge,
+
+    /// Cells within the given grid distance `k` of each other are adjacent.
+    GridDisk(u32),
+}
+
+impl Adjacency {
+    const fn k(&self) -> u32 {
+        match self {
+            Self::Edge => 1,
+            Self::GridDisk(k) => *k,
+        }
+    }
+}
+
+/// Splits `cells` into the sets of cells connected via the given `adjacency`.
+///
+/// The components are ordered by the smallest cell contained in each of them. Each component
+/// can be converted to polygons using [`ToLinkedPolygons`](crate::ToLinkedPolygons).
+pub fn connected_components<I>(cells: I, adjacency: Adjacency) -> Result<Vec<H3CellSet>, Error>
+where
+    I: IntoIterator<Item = H3Cell>,
+{
+    let mut remaining: H3CellSet = cells.into_iter().collect();
+    let mut builder = GridDiskBuilder::create(1, adjacency.k())?;
+    let mut components = Vec::new();
+
+    let mut start_cells: Vec<_> = remaining.iter().copied().collect();
+    start_cells.sort_unstable();
+    for start_cell in start_cells {
+        if !remaining.remove(&start_cell) {
+            // already part of a previous component
+            continue;
+        }
+        let mut component = H3CellSet::default();
+        let mut queue = vec![start_cell];
+        while let Some(cell) = queue.pop() {
+            for (neighbor, _) in builder.build_grid_disk(&cell)? {
+                if remaining.remove(&neighbor) {
+                    queue.push(neighbor);
+                }
+            }
+            component.insert(cell);
+        }
+        components.push(component);
+    }
+    Ok(components)
+}
+
+/// The outward facing directed edges of `cells` - all edges having their origin
+/// in `cells` and their destination outside of it.
+///
+/// The edges are sorted.
+pub fn boundary_directed_edges(cells: &H3CellSet) -> Result<Vec<H3DirectedEdge>, Error> {
+    let mut edges = Vec::new();
+    for cell in cells.iter() {
+        for edge in cell.directed_edges()?.iter() {
+            if !cells.contains(&edge.destination_cell()?) {
+                edges.push(edge);
+            }
+        }
+    }
+    edges.sort_unstable();
+    Ok(edges)
+}
+
+/// The exact outlines of `cells`, built by joining the [`H3DirectedEdge::boundary_linestring`]s
+/// of the [`boundary_directed_edges`] to closed rings.
+///
+/// Outer rings are oriented counter-clockwise, the rings around holes clockwise. In
+/// contrast to [`ToLinkedPolygons`](crate::ToLinkedPolygons) no smoothing is applied and the
+/// rings are not assembled to polygons.
+pub fn boundary_outlines(cells: &H3CellSet) -> Result<MultiLineString<f64>, Error> {
+    Ok(MultiLineString(join_to_rings(&boundary_directed_edges(
+        cells,
+    )?)?))
+}
+
+/// Joins the boundary linestrings of directed edges to closed rings.
+///
+/// The edges get chained using the [`H3Vertex`]es they start and end at, the coordinates
+/// are only looked up once a ring is closed.
+///
+/// Fails with [`Error::Failed`] when the edges can not be joined to closed rings.
+pub(crate) fn join_to_rings(edges: &[H3DirectedEdge]) -> Result<Vec<LineString<f64>>, Error> {
+    let edge_vertexes = edges
+        .iter()
+        .map(edge_vertexes)
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut edges_by_start: HashMap<H3Vertex, Vec<usize>> = HashMap::default();
+    for (i, (start, _)) in edge_vertexes.iter().enumerate() {
+        edges_by_start.entry(*start).or_default().push(i);
+    }
+
+    let mut used = vec![false; edges.len()];
+    let mut rings = Vec::new();
+    for first_i in 0..edges.len() {
+        if used[first_i] {
+            continue;
+        }
+        let ring_start = edge_vertexes[first_i].0;
+        let mut ring_edges = vec![first_i];
+        used[first_i] = true;
+        let mut end = edge_vertexes[first_i].1;
+        while end != ring_start {
+            let next_i = edges_by_start
+                .get(&end)
+                .and_then(|candidates| candidates.iter().find(|j| !used[**j]).copied())
+                .ok_or(Error::Failed)?;
+            used[next_i] = true;
+            ring_edges.push(next_i);
+            end = edge_vertexes[next_i].1;
+        }
+
+        let mut coordinates: Vec<Coordinate<f64>> = Vec::with_capacity(ring_edges.len() * 2 + 1);
+        for i in ring_edges {
+            let linestring = edges[i].boundary_linestring()?;
+            let skip = usize::from(!coordinates.is_empty());
+            coordinates.extend(linestring.0.into_iter().skip(skip));
+        }
+        // close the ring using the exact starting coordinate
+        if let Some(first) = coordinates.first().copied() {
+            if let Some(last) = coordinates.last_mut() {
+                *last = first;
+            }
+        }
+        rings.push(LineString::from(coordinates));
+    }
+    Ok(rings)
+}
+
+/// The vertexes the boundary of `edge` starts and ends at.
+///
+/// These are the two vertexes shared by the origin and the destination cell. The vertexes of a
+/// cell are ordered counter-clockwise, and so is the boundary of an edge.
+fn edge_vertexes(edge: &H3DirectedEdge) -> Result<(H3Vertex, H3Vertex), Error> {
+    let origin_vertexes: Vec<_> = edge.origin_cell()?.vertexes()?.iter().collect();
+    let destination_vertexes = edge.destination_cell()?.vertexes()?;
+    let shared: Vec<_> = origin_vertexes
+        .iter()
+        .enumerate()
+        .filter(|(_, vertex)| destination_vertexes.iter().any(|v| v == **vertex))
+        .map(|(pos, _)| pos)
+        .collect();
+    match shared[..] {
+        [a, b] if b == a + 1 => Ok((origin_vertexes[a], origin_vertexes[b])),
+        [0, b] if b + 1 == origin_vertexes.len() => Ok((origin_vertexes[b], origin_vertexes[0])),
+        _ => Err(Error::Failed),
+    }
+}
+
+/// Assembles rings built by [`join_to_rings`] to polygons.
+///
+/// Counter-clockwise rings form the exteriors of the polygons, clockwise rings are holes. Each
+/// hole gets assigned to the smallest exterior containing it. The rings must not cross the
+/// antimeridian - see [`normalize_polygon`](crate::antimeridian::normalize_polygon).
+///
+/// Fails with [`Error::Failed`] for holes not contained in any exterior.
+pub(crate) fn rings_to_polygons(rings: Vec<LineString<f64>>) -> Result<Vec<Polygon<f64>>, Error> {
+    let (exteriors, holes): (Vec<_>, Vec<_>) = rings
+        .into_iter()
+        .map(|ring| (signed_area(&ring), ring))
+        .partition(|(area, _)| *area >= 0.0);
+
+    let mut polygons: Vec<_> = exteriors
+        .into_iter()
+        .map(|(area, ring)| (area, Polygon::new(ring, vec![])))
+        .collect();
+    for (_, hole) in holes {
+        // the midpoint of a segment of a hole can not be located on the exterior ring
+        let point = match hole.lines().next() {
+            Some(line) => Point::new(
+                (line.start.x + line.end.x) / 2.0,
+                (line.start.y + line.end.y) / 2.0,
+            ),
+            None => continue,
+        };
+        let exterior_i = polygons
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, poly))| poly.contains(&point))
+            .min_by(|(_, (area_a, _)), (_, (area_b, _))| {
+                area_a.partial_cmp(area_b).unwrap_or(Ordering::Equal)
+            })
+            .map(|(i, _)| i)
+            .ok_or(Error::Failed)?;
+        polygons[exterior_i].1.interiors_push(hole);
+    }
+    Ok(polygons.into_iter().map(|(_, poly)| poly).collect())
+}
+
+/// signed area of a ring using the shoelace formula. Positive for counter-clockwise rings.
+fn signed_area(ring: &LineString<f64>) -> f64 {
+    ring.0
+        .windows(2)
+        .map(|w| w[0].x.mul_add(w[1].y, -(w[1].x * w[0].y)))
+        .sum::<f64>()
+        / 2.0
+}
+
+/// The length of the outlines of `cells` in meters.
+pub fn perimeter_m(cells: &H3CellSet) -> Result<f64, Error> {
+    boundary_directed_edges(cells)?
+        .iter()
+        .try_fold(0.0, |length, edge| Ok(length + edge.exact_length_m()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use geo::algorithm::haversine_length::HaversineLength;
+
+    use crate::collections::H3CellSet;
+    use crate::{H3Cell, ToCoordinate, ToLinkedPolygons};
+
+    use super::*;
+
+    fn center_cell() -> H3Cell {
+        H3Cell::try_from(0x89283080ddbffff_u64).unwrap()
+    }
+
+    /// a disk of radius 1 around the center and a ring at distance 4
+    fn disk_and_ring() -> (H3CellSet, H3CellSet) {
+        let disk = center_cell().grid_disk(1).unwrap().iter().collect();
+        let ring = center_cell().grid_ring(4).unwrap().iter().collect();
+        (disk, ring)
+    }
+
+    #[test]
+    fn edge_adjacency() {
+        let (disk, ring) = disk_and_ring();
+        let components = connected_components(
+            disk.iter().chain(ring.iter()).copied(),
+            Adjacency::default(),
+        )
+        .unwrap();
+        assert_eq!(components.len(), 2);
+        assert!(components.contains(&disk));
+        assert!(components.contains(&ring));
+
+        for component in components.iter() {
+            let polygons = component.to_linked_polygons(false).unwrap();
+            assert_eq!(polygons.len(), 1);
+        }
+    }
+
+    #[test]
+    fn grid_disk_adjacency() {
+        let (disk, ring) = disk_and_ring();
+        let cells = || disk.iter().chain(ring.iter()).copied();
+        assert_eq!(
+            connected_components(cells(), Adjacency::GridDisk(2))
+                .unwrap()
+                .len(),
+            2
+        );
+        let components = connected_components(cells(), Adjacency::GridDisk(3)).unwrap();
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].len(), disk.len() + ring.len());
+    }
+
+    #[test]
+    fn deterministic_order() {
+        let (disk, ring) = disk_and_ring();
+        let components_a =
+            connected_components(disk.iter().chain(ring.iter()).copied(), Adjacency::Edge).unwrap();
+        let components_b =
+            connected_components(ring.iter().chain(disk.iter()).copied(), Adjacency::Edge).unwrap();
+        assert_eq!(components_a, components_b);
+        assert!(connected_components(vec![], Adjacency::Edge)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn boundary_of_disk() {
+        let (disk, _) = disk_and_ring();
+        let edges = boundary_directed_edges(&disk).unwrap();
+        assert_eq!(edges.len(), 18);
+        assert!(edges
+            .iter()
+            .all(|edge| !disk.contains(&edge.destination_cell().unwrap())));
+
+        let outlines = boundary_outlines(&disk).unwrap();
+        assert_eq!(outlines.0.len(), 1);
+        assert!(outlines.0[0].is_closed());
+        assert_eq!(outlines.0[0].0.len(), 19);
+
+        let perimeter = perimeter_m(&disk).unwrap();
+        assert!((outlines.haversine_length() - perimeter).abs() / perimeter < 1e-3);
+    }
+
+    #[test]
+    fn boundary_with_hole() {
+        let mut cells: H3CellSet = center_cell().grid_disk(2).unwrap().iter().collect();
+        cells.remove(&center_cell());
+
+        let edges = boundary_directed_edges(&cells).unwrap();
+        assert_eq!(edges.len(), 30 + 6);
+
+        let outlines = boundary_outlines(&cells).unwrap();
+        assert_eq!(outlines.0.len(), 2);
+        assert!(outlines.0.iter().all(LineString::is_closed));
+
+        assert!(boundary_directed_edges(&H3CellSet::default())
+            .unwrap()
+            .is_empty());
+        assert!(boundary_outlines(&H3CellSet::default())
+            .unwrap()
+            .0
+            .is_empty());
+    }
+
+    #[test]
+    fn join_broken_chain() {
+        let mut cells: H3CellSet = center_cell().grid_disk(1).unwrap().iter().collect();
+        cells.remove(&center_cell());
+        let mut edges = boundary_directed_edges(&cells).unwrap();
+        assert_eq!(join_to_rings(&edges).unwrap().len(), 2);
+
+        edges.remove(0);
+        assert!(join_to_rings(&edges).is_err());
+    }
+
+    #[test]
+    fn rings_to_polygons_orphan_hole() {
+        let mut cells: H3CellSet = center_cell().grid_disk(1).unwrap().iter().collect();
+        cells.remove(&center_cell());
+        let rings = join_to_rings(&boundary_directed_edges(&cells).unwrap()).unwrap();
+        let polygons = rings_to_polygons(rings.clone()).unwrap();
+        assert_eq!(polygons.len(), 1);
+        assert_eq!(polygons[0].interiors().len(), 1);
+
+        // only the clockwise ring around the hole remains
+        let holes: Vec<_> = rings
+            .into_iter()
+            .filter(|ring| signed_area(ring) < 0.0)
+            .collect();
+        assert_eq!(holes.len(), 1);
+        assert!(rings_to_polygons(holes).is_err());
+    }
+
+    #[test]
+    fn edge_vertexes_are_shared() {
+        let pentagon = H3Cell::try_from(0x8009fffffffffff_u64)
+            .unwrap()
+            .center_child(1)
+            .unwrap();
+        assert!(pentagon.is_pentagon());
+        for edge in center_cell()
+            .directed_edges()
+            .unwrap()
+            .iter()
+            .chain(pentagon.directed_edges().unwrap().iter())
+        {
+            let (start, end) = edge_vertexes(&edge).unwrap();
+            let linestring = edge.boundary_linestring().unwrap();
+            let start_c = start.to_coordinate().unwrap();
+            let end_c = end.to_coordinate().unwrap();
+            assert!((linestring.0[0].x - start_c.x).abs() < 1e-9);
+            assert!((linestring.0[0].y - start_c.y).abs() < 1e-9);
+            assert!((linestring.0[linestring.0.len() - 1].x - end_c.x).abs() < 1e-9);
+
+            // the reversed edge uses the same vertexes in the opposite direction
+            let reversed = edge.reversed().unwrap();
+            assert_eq!(edge_vertexes(&reversed).unwrap(), (end, start));
+        }
+    }
+}
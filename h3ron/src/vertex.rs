@@ -0,0 +1,223 @@
+use std::fmt::{self, Debug, Display, Formatter, LowerHex};
+use std::ops::Deref;
+use std::str::FromStr;
+
+use geo_types::{Coordinate, Point};
+#[cfg(feature = "use-serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use h3ron_h3_sys::H3Index;
+
+#[cfg(feature = "use-serde")]
+use crate::index::{deserialize_index, serialize_index};
+use crate::index::{index_from_str, Index};
+use crate::{Error, FromH3Index, ToCoordinate};
+
+/// H3 Index representing a H3 vertex - a corner of a H3 cell.
+///
+/// Vertexes are shared between neighboring cells, so all cells touching a corner
+/// will yield the same `H3Vertex` for it.
+#[derive(PartialOrd, PartialEq, Clone, Hash, Eq, Ord, Copy)]
+#[repr(transparent)]
+pub struct H3Vertex(H3Index);
+
+impl Debug for H3Vertex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "H3Vertex({})", self)
+    }
+}
+
+/// convert to index including validation
+impl TryFrom<u64> for H3Vertex {
+    type Error = Error;
+
+    fn try_from(h3index: H3Index) -> Result<Self, Self::Error> {
+        let index = Self::new(h3index);
+        index.validate()?;
+        Ok(index)
+    }
+}
+
+impl FromH3Index for H3Vertex {
+    fn from_h3index(h3index: H3Index) -> Self {
+        Self::new(h3index)
+    }
+}
+
+impl Index for H3Vertex {
+    fn h3index(&self) -> H3Index {
+        self.0
+    }
+
+    fn new(h3index: H3Index) -> Self {
+        Self(h3index)
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        if unsafe { h3ron_h3_sys::isValidVertex(self.h3index()) == 0 } {
+            Err(Error::VertexInvalid)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl H3Vertex {
+    /// Build the `Point` of the location of the vertex.
+    pub fn to_point(&self) -> Result<Point<f64>, Error> {
+        self.to_coordinate().map(Point::from)
+    }
+}
+
+impl Display for H3Vertex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:x}", self.0)
+    }
+}
+
+impl LowerHex for H3Vertex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        LowerHex::fmt(&self.0, f)
+    }
+}
+
+impl FromStr for H3Vertex {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        index_from_str(s)
+    }
+}
+
+/// Serialized as hex string for human-readable formats like JSON, as `u64` otherwise.
+#[cfg(feature = "use-serde")]
+impl Serialize for H3Vertex {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_index(self, "H3Vertex", serializer)
+    }
+}
+
+#[cfg(feature = "use-serde")]
+impl<'de> Deserialize<'de> for H3Vertex {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_index("H3Vertex", deserializer)
+    }
+}
+
+impl ToCoordinate for H3Vertex {
+    type Error = Error;
+
+    /// the coordinate of the vertex
+    fn to_coordinate(&self) -> Result<Coordinate<f64>, Self::Error> {
+        let mut ll = h3ron_h3_sys::LatLng { lat: 0.0, lng: 0.0 };
+        Error::check_returncode(unsafe { h3ron_h3_sys::vertexToLatLng(self.0, &mut ll) })
+            .map(|_| ll.into())
+    }
+}
+
+impl Deref for H3Vertex {
+    type Target = H3Index;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    #[cfg(feature = "use-serde")]
+    use bincode::{deserialize, serialize};
+
+    use crate::collections::HashSet;
+    use crate::{H3Cell, H3Vertex, Index, ToCoordinate, ToPolygon};
+
+    #[test]
+    fn vertex_of_cell() {
+        let cell = H3Cell::try_from(0x8a1fb46622dffff_u64).unwrap();
+        let vertex = cell.vertex(3).unwrap();
+        assert_eq!(vertex, H3Vertex::try_from(0x25a1fb464492ffff_u64).unwrap());
+        assert!(vertex.is_valid());
+    }
+
+    #[test]
+    fn vertex_out_of_range() {
+        let cell = H3Cell::try_from(0x8a1fb46622dffff_u64).unwrap();
+        assert!(cell.vertex(6).is_err());
+    }
+
+    #[test]
+    fn vertexes_of_hexagon() {
+        let cell = H3Cell::try_from(0x89283080ddbffff_u64).unwrap();
+        let vertexes = cell.vertexes().unwrap();
+        assert_eq!(vertexes.count(), 6);
+        assert!(vertexes.iter().all(|vertex| vertex.is_valid()));
+    }
+
+    #[test]
+    fn vertexes_of_pentagon() {
+        let cell = H3Cell::try_from(0x821c07fffffffff_u64).unwrap();
+        assert!(cell.is_pentagon());
+        assert_eq!(cell.vertexes().unwrap().count(), 5);
+    }
+
+    #[test]
+    fn vertexes_are_shared_between_neighbors() {
+        let cell = H3Cell::try_from(0x89283080ddbffff_u64).unwrap();
+        let vertexes: HashSet<_> =
+            cell.grid_disk(1)
+                .unwrap()
+                .iter()
+                .fold(HashSet::default(), |mut vertexes, cell| {
+                    vertexes.extend(cell.vertexes().unwrap().iter());
+                    vertexes
+                });
+        // 6 vertexes of the center cell + 3 outer vertexes for each of the 6 neighbors
+        assert_eq!(vertexes.len(), 24);
+    }
+
+    #[test]
+    fn vertex_coordinate_is_cell_corner() {
+        let cell = H3Cell::try_from(0x89283080ddbffff_u64).unwrap();
+        let corners = cell.to_polygon().unwrap().exterior().0.clone();
+        for vertex in cell.vertexes().unwrap().iter() {
+            let c = vertex.to_coordinate().unwrap();
+            assert!(corners
+                .iter()
+                .any(|corner| (corner.x - c.x).abs() < 1e-9 && (corner.y - c.y).abs() < 1e-9));
+        }
+    }
+
+    #[test]
+    fn invalid_vertex() {
+        assert!(H3Vertex::try_from(0x89283080ddbffff_u64).is_err());
+        assert!(!H3Vertex::new(0).is_valid());
+    }
+
+    #[test]
+    fn string_roundtrip() {
+        let vertex = H3Vertex::try_from(0x25a1fb464492ffff_u64).unwrap();
+        assert_eq!(vertex.to_string(), "25a1fb464492ffff".to_string());
+        assert_eq!(H3Vertex::from_str("25a1fb464492ffff").unwrap(), vertex);
+        assert_eq!(
+            format!("{:?}", vertex),
+            "H3Vertex(25a1fb464492ffff)".to_string()
+        );
+    }
+
+    #[cfg(feature = "use-serde")]
+    #[test]
+    fn serde_roundtrip() {
+        let vertex = H3Vertex::new(0x25a1fb464492ffff_u64);
+        let serialized_data = serialize(&vertex).unwrap();
+        let vertex_2: H3Vertex = deserialize(&serialized_data).unwrap();
+        assert_eq!(vertex, vertex_2);
+    }
+}